@@ -1,10 +1,33 @@
 use crossterm::event::KeyModifiers;
 use etui::{
-    containers::frame::Frame,
+    containers::{
+        frame::Frame,
+        grid::Grid,
+        list_view::{ListView, SelectionMode},
+        menu::{MenuItem, SubMenu},
+        menu_bar::MenuBar,
+        modal::Modal,
+        scroll_area::ScrollArea,
+        split_pane::{Side, SplitPane},
+        window::Window,
+    },
+    layout::{Align, Aligned, Constraint, Justify, Split},
     math_util::VecI2,
     start_app,
     style::{Color, DefaultStyle, FromHSV, Style, StyledText},
-    widgets::{progress_bar::ProgressBar, spinner::Spinner},
+    undo::Undoer,
+    widgets::{
+        combo_box::ComboBox,
+        drag_value::DragValue,
+        progress_bar::ProgressBar,
+        slider::Slider,
+        spinner::Spinner,
+        table::{Column, ColumnWidth, Table},
+        text_area::TextArea,
+        text_edit::TextEdit,
+        toggle::Toggle,
+        tree_view::TreeView,
+    },
     App,
 };
 
@@ -17,6 +40,12 @@ pub struct MyApp {
 
     progress_bar: ProgressBars,
     drop_downs: DropDowns,
+    kanban: Kanban,
+    editor: String,
+    lists: Lists,
+    show_report: bool,
+    show_notes: bool,
+    notes: String,
 
     cursor: VecI2,
     clicked: bool,
@@ -29,6 +58,12 @@ impl Default for MyApp {
             show_side: false,
             progress_bar: ProgressBars::new(),
             drop_downs: DropDowns::new(),
+            kanban: Kanban::new(),
+            lists: Lists::new(),
+            show_report: false,
+            show_notes: false,
+            notes: "Drag the title bar to move a window\nand its borders to resize it\n".into(),
+            editor: "fn main() {\n    println!(\"Hello, world!\");\n}\n".into(),
             cursor: VecI2::default(),
             clicked: false,
             show: false,
@@ -45,8 +80,58 @@ impl App for MyApp {
         self.virtual_mouse(ctx);
 
         Frame::new().show(ctx, |ui| {
+            MenuBar::new().show(ui, |bar| {
+                bar.menu("&File", |ui| {
+                    if MenuItem::new("New note").show(ui).clicked() {
+                        self.notes.clear();
+                        self.show_notes = true;
+                    }
+                    SubMenu::new("Recent").show(ui, |ui| {
+                        MenuItem::new("Nothing yet").enabled(false).show(ui);
+                    });
+                });
+                bar.menu("&View", |ui| {
+                    if MenuItem::new("UI Info").show(ui).clicked() {
+                        self.show_side ^= true;
+                    }
+                    if MenuItem::new("Windows").show(ui).clicked() {
+                        self.show_report = true;
+                        self.show_notes = true;
+                    }
+                    ui.seperator();
+                    SubMenu::new("Style").show(ui, |ui| {
+                        if MenuItem::new("Unicode").show(ui).clicked() {
+                            *ui.ctx().style().borrow_mut() = DefaultStyle::new_unicode();
+                        }
+                        if MenuItem::new("Ascii").show(ui).clicked() {
+                            *ui.ctx().style().borrow_mut() = DefaultStyle::new_ascii();
+                        }
+                    });
+                });
+                bar.menu("&Help", |ui| {
+                    MenuItem::new("Focus the menu bar")
+                        .shortcut("F10")
+                        .enabled(false)
+                        .show(ui);
+                    MenuItem::new("Context menu")
+                        .shortcut("Shift+F10")
+                        .enabled(false)
+                        .show(ui);
+                });
+            });
             ui.horizontal(|ui| {
-                self.show_side ^= ui.button("UI Info").clicked();
+                self.show_side ^= ui
+                    .button("UI Info")
+                    .on_hover_text(ui.ctx(), "Frame statistics and style switches")
+                    .clicked();
+                let windows = ui.button("Windows").on_hover_ui(ui.ctx(), |ui| {
+                    ui.label("Opens floating windows,");
+                    ui.label("drag them by the title bar");
+                });
+                if windows.clicked() {
+                    self.show_report = true;
+                    self.show_notes = true;
+                }
                 ui.seperator();
                 ui.label(StyledText::styled(
                     "F1 to enable arrow/enter cursor",
@@ -80,7 +165,17 @@ impl App for MyApp {
                 ui.vertical(|ui| {
                     ui.tabbed_area(
                         etui::id::Id::new("TABS"),
-                        ["Colors", "Progress Bar", "Drop Downs", "Input", "Layouts"],
+                        [
+                            "Colors",
+                            "Progress Bar",
+                            "Drop Downs",
+                            "Input",
+                            "Layouts",
+                            "Drag & Drop",
+                            "Editor",
+                            "Scrolling",
+                            "Lists",
+                        ],
                         |tab, ui| {
                             ui.bordered(|ui| {
                                 ui.with_size(ui.get_max().size(), |ui| match tab {
@@ -89,6 +184,16 @@ impl App for MyApp {
                                     2 => self.drop_downs.ui(ui),
                                     3 => ui.ctx().clone().input().ui(ui),
                                     4 => layout_fun(ui),
+                                    5 => self.kanban.ui(ui),
+                                    6 => {
+                                        let size = ui.get_max().size();
+                                        TextArea::new(&mut self.editor)
+                                            .line_numbers(true)
+                                            .desired_size(size)
+                                            .show(ui);
+                                    }
+                                    7 => scrolling(ui),
+                                    8 => self.lists.ui(ui),
                                     _ => {
                                         let mut text = StyledText::new("How did you get here?");
                                         text.bg(crossterm::style::Color::Red);
@@ -101,59 +206,350 @@ impl App for MyApp {
                 });
             });
         });
+
+        Window::new("Frame report")
+            .open(&mut self.show_report)
+            .default_pos(VecI2::new(4, 6))
+            .default_size(VecI2::new(28, 7))
+            .show(ctx, |ui| {
+                let report = ui.ctx().previous_frame_report();
+                let rows = [
+                    ("frame", ui.ctx().get_frame()),
+                    ("bytes written", report.bytes_written),
+                    ("bytes buffered", report.total_text_len),
+                    ("styles buffered", report.total_styles),
+                ];
+                Grid::new("frame report")
+                    .striped(true)
+                    .align(1, Align::End)
+                    .show(ui, |grid| {
+                        for (name, value) in rows {
+                            grid.cell(|ui| ui.label(name));
+                            grid.cell(|ui| ui.label(value.to_string()));
+                            grid.end_row();
+                        }
+                    });
+            });
+        Window::new("Notes")
+            .open(&mut self.show_notes)
+            .default_pos(VecI2::new(24, 10))
+            .default_size(VecI2::new(40, 10))
+            .show(ctx, |ui| {
+                let size = ui.get_max().size();
+                TextArea::new(&mut self.notes).desired_size(size).show(ui);
+            });
     }
 }
 
-fn layout_fun(ui: &mut etui::ui::Ui) {
-    use etui::ui::Layout::*;
-    let mut max = ui.get_max();
-
-    let used = ui.vertical(|ui| {
-        ui.with_layout(TopLeftHorizontal, |ui| {
-            ui.bordered(|ui| {
-                ui.label("TopLeft\nHorizontal");
-                ui.label("TopLeftHorizontal");
-            });
+fn scrolling(ui: &mut etui::ui::Ui) {
+    ui.horizontal(|ui| {
+        ScrollArea::vertical("list").max_height(12).show(ui, |ui| {
+            for i in 0..50 {
+                ui.button(format!("Item {i}"));
+            }
         });
+        ui.add_space_primary_direction(2);
+        // only labels inside, focus lets page up/down scroll it
+        ScrollArea::both("text")
+            .max_width(40)
+            .max_height(12)
+            .focusable(true)
+            .show(ui, |ui| {
+                for row in 0..30 {
+                    ui.label(format!(
+                        "{row:>2} {}",
+                        "lorem ipsum dolor sit amet ".repeat(3)
+                    ));
+                }
+            });
+    });
+}
 
-        ui.with_layout(BottomLeftHorizontal, |ui| {
-            ui.vertical(|ui| {
-                ui.bordered(|ui| {
-                    ui.label("TopLeft\nHorizontal");
-                    ui.label("TopLeftHorizontal");
+struct Process {
+    pid: u32,
+    name: String,
+    cpu: f32,
+    memory: u64,
+}
+
+struct Lists {
+    log_lines: usize,
+    last_line: std::time::Instant,
+    opened: Option<usize>,
+    selected: usize,
+    processes: Vec<Process>,
+    /// processes waiting for the kill to be confirmed
+    to_kill: Vec<usize>,
+    error: Option<String>,
+}
+
+impl Lists {
+    fn new() -> Self {
+        Self {
+            log_lines: 20,
+            last_line: std::time::Instant::now(),
+            opened: None,
+            selected: 0,
+            processes: (0..100_000u32)
+                .map(|i| {
+                    let name = ["init", "shell", "editor", "server", "daemon"][i as usize % 5];
+                    let load = (i.wrapping_mul(2654435761) % 1000) as f32 / 10.0;
+                    Process {
+                        pid: i + 1,
+                        name: format!("{name}-{i}"),
+                        cpu: load,
+                        memory: (i as u64 * 7919) % 65536,
+                    }
+                })
+                .collect(),
+            to_kill: Vec::new(),
+            error: None,
+        }
+    }
+
+    fn ui(&mut self, ui: &mut etui::ui::Ui) {
+        if self.last_line.elapsed() > std::time::Duration::from_millis(250) {
+            self.last_line = std::time::Instant::now();
+            self.log_lines += 1;
+        }
+
+        let opened = match self.opened {
+            Some(row) => format!("opened row {row}"),
+            None => "enter or double click a row to open it".into(),
+        };
+        ui.label(format!("{} selected, {opened}", self.selected));
+        ui.horizontal(|ui| {
+            let list = ListView::new("million", 1_000_000)
+                .selection_mode(SelectionMode::Multiple)
+                .desired_size(VecI2::new(30, 12))
+                .show(ui, |ui, row| {
+                    ui.label(format!("Row {:>7}", row.index));
                 });
-                ui.bordered(|ui| {
-                    ui.with_layout(TopLeftHorizontal, |ui| {
-                        ui.label("In between");
-                        ui.add_vertical_space(ui.get_max().height)
-                    });
+            self.selected = list.selection.len();
+            if let Some(row) = list.activated {
+                self.opened = Some(row);
+            }
+            list.response.context_menu(ui.ctx(), |ui| {
+                if MenuItem::new(format!("Open row {}", list.cursor))
+                    .shortcut("Enter")
+                    .show(ui)
+                    .clicked()
+                {
+                    self.opened = Some(list.cursor);
+                }
+                SubMenu::new("Log").show(ui, |ui| {
+                    if MenuItem::new("Add 100 lines").show(ui).clicked() {
+                        self.log_lines += 100;
+                    }
+                    if MenuItem::new("Clear")
+                        .enabled(self.log_lines > 0)
+                        .show(ui)
+                        .clicked()
+                    {
+                        self.log_lines = 0;
+                    }
                 });
+                ui.seperator();
+                MenuItem::new("Properties").enabled(false).show(ui);
+            });
+            ui.add_space_primary_direction(2);
+            ui.vertical(|ui| {
+                ui.label(format!(
+                    "{} lines, scroll up to stop following",
+                    self.log_lines
+                ));
+                ListView::new("log", self.log_lines)
+                    .stick_to_bottom(true)
+                    .row_heights(|row| if row % 5 == 0 { 2 } else { 1 })
+                    .desired_size(VecI2::new(40, 11))
+                    .show(ui, |ui, row| {
+                        if row.index % 5 == 0 {
+                            ui.label(format!("[{:>5}] checkpoint\n        all good", row.index));
+                        } else {
+                            ui.label(format!("[{:>5}] tick", row.index));
+                        }
+                    });
             });
         });
-        ui.get_current()
-    });
-    max.width -= (used.x + used.width) - max.x;
-    max.x = used.top_right().x;
-
-    ui.set_max(max);
 
-    let used = ui.with_layout(TopRightHorizontal, |ui| {
-        ui.bordered(|ui| {
-            ui.label("TopRight\nHorizontal");
-            ui.label("TopRightHorizontal");
+        ui.add_space_primary_direction(1);
+        let table = ui.horizontal(|ui| {
+            let table = Table::new("processes")
+                .selection_mode(SelectionMode::Multiple)
+                .column(
+                    Column::new("pid", |p: &Process| p.pid.to_string())
+                        .width(ColumnWidth::Fixed(7))
+                        .sort_by_key(|p| p.pid),
+                )
+                .column(
+                    Column::new("name", |p: &Process| p.name.clone())
+                        .width(ColumnWidth::Fraction(1.0))
+                        .sort_by(|a, b| a.name.cmp(&b.name)),
+                )
+                .column(
+                    Column::new("cpu %", |p: &Process| format!("{:>5.1}", p.cpu))
+                        .sort_by(|a, b| a.cpu.total_cmp(&b.cpu)),
+                )
+                .column(
+                    Column::new("memory", |p: &Process| format!("{} KiB", p.memory))
+                        .sort_by_key(|p| p.memory),
+                )
+                .desired_size(VecI2::new(72, 12))
+                .show(ui, &self.processes);
+            ui.add_space_primary_direction(2);
+            // a made up directory tree with 10 entries per directory, 4 levels deep
+            TreeView::new("files", ["/".to_string()], |dir: &String| {
+                (0..10).map(|i| format!("{}{i}/", dir)).collect()
+            })
+            .has_children(|path| path.matches('/').count() < 5)
+            .selection_mode(SelectionMode::Multiple)
+            .desired_size(VecI2::new(40, 12))
+            .show(ui, |ui, row| {
+                let name = row
+                    .node
+                    .trim_end_matches('/')
+                    .rsplit('/')
+                    .next()
+                    .unwrap_or("");
+                if row.has_children {
+                    ui.label(format!("dir {name}"));
+                } else {
+                    ui.label(format!("file {name}.txt"));
+                }
+            });
+            table
         });
-        ui.get_current()
-    });
 
-    max.height -= (used.y) - max.y;
-    max.y = used.top_right().y;
-    ui.set_max(max);
+        ui.add_space_primary_direction(1);
+        if ui.button("Kill selected processes").clicked() {
+            if table.selection.is_empty() {
+                self.error = Some("Select some processes to kill first".into());
+            } else {
+                self.to_kill = table.selected_rows().collect();
+            }
+        }
 
-    ui.with_layout(BottomRightHorizontal, |ui| {
-        ui.bordered(|ui| {
-            ui.label("BottomRight\nHorizontal");
-            ui.label("BottomRightHorizontal");
-        });
+        let ctx = ui.ctx().clone();
+        if !self.to_kill.is_empty() {
+            let message = format!("Kill {} processes?", self.to_kill.len());
+            let response = Modal::new("kill")
+                .title("Kill")
+                .confirm(&ctx, &message, "Kill", "Cancel");
+            if response.result == Some(true) {
+                self.to_kill.sort_unstable();
+                for index in self.to_kill.iter().rev() {
+                    self.processes.remove(*index);
+                }
+            }
+            if response.closed() {
+                self.to_kill.clear();
+            }
+        }
+        if let Some(error) = &self.error {
+            let response = Modal::new("error").title("Error").show(&ctx, |ui| {
+                ui.label(error.as_str());
+                ui.add_space_primary_direction(1);
+                ui.button("Ok").clicked().then_some(())
+            });
+            if response.closed() {
+                self.error = None;
+            }
+        }
+    }
+}
+
+fn layout_fun(ui: &mut etui::ui::Ui) {
+    use etui::ui::Layout::*;
+
+    let rows = [
+        Constraint::Fill(1),
+        Constraint::Length(1),
+        Constraint::Length(3),
+    ];
+    Split::vertical(rows).show(ui, |i, ui| {
+        if i == 1 {
+            ui.horizontal(|ui| {
+                ui.aligned(Justify::SpaceBetween, Align::Start, |ui| {
+                    ui.button("Spread");
+                    ui.button("between");
+                    ui.button("the row");
+                });
+            });
+            return;
+        }
+        if i == 2 {
+            Split::horizontal([Constraint::Ratio(1, 4); 4])
+                .spacing(1)
+                .show(ui, |column, ui| {
+                    let label = format!("Column {}", column + 1);
+                    if column == 3 {
+                        Aligned::new("stretched")
+                            .stretch(true)
+                            .show(ui, |ui| ui.bordered(|ui| ui.label(label)));
+                        return;
+                    }
+                    let align = [Align::Start, Align::Center, Align::End][column];
+                    ui.aligned(Justify::Start, align, |ui| {
+                        ui.bordered(|ui| ui.label(label));
+                    });
+                });
+            return;
+        }
+        SplitPane::horizontal("layouts")
+            .default_ratio(0.4)
+            .min_size(Side::First, 20)
+            .min_size(Side::Second, 24)
+            .collapsible(true)
+            .show(
+                ui,
+                |ui| {
+                    ui.with_layout(TopLeftHorizontal, |ui| {
+                        ui.bordered(|ui| {
+                            ui.label("TopLeft\nHorizontal");
+                            ui.label("TopLeftHorizontal");
+                        });
+                    });
+
+                    ui.with_layout(BottomLeftHorizontal, |ui| {
+                        ui.vertical(|ui| {
+                            ui.bordered(|ui| {
+                                ui.label("TopLeft\nHorizontal");
+                                ui.label("TopLeftHorizontal");
+                            });
+                            ui.bordered(|ui| {
+                                ui.with_layout(TopLeftHorizontal, |ui| {
+                                    ui.label("In between");
+                                    ui.add_vertical_space(ui.get_max().height)
+                                });
+                            });
+                        });
+                    });
+                },
+                |ui| {
+                    SplitPane::vertical("layouts right")
+                        .min_size(Side::First, 4)
+                        .min_size(Side::Second, 4)
+                        .show(
+                            ui,
+                            |ui| {
+                                ui.with_layout(TopRightHorizontal, |ui| {
+                                    ui.bordered(|ui| {
+                                        ui.label("TopRight\nHorizontal");
+                                        ui.label("TopRightHorizontal");
+                                    });
+                                });
+                            },
+                            |ui| {
+                                ui.with_layout(BottomRightHorizontal, |ui| {
+                                    ui.bordered(|ui| {
+                                        ui.label("BottomRight\nHorizontal");
+                                        ui.label("BottomRightHorizontal");
+                                    });
+                                });
+                            },
+                        );
+                },
+            );
     });
 }
 
@@ -201,8 +597,7 @@ impl MyApp {
             .input()
             .keyboard
             .pressed
-            .get(&crossterm::event::KeyCode::F(1))
-            .is_some()
+            .contains_key(&crossterm::event::KeyCode::F(1))
         {
             self.show ^= true;
         }
@@ -448,14 +843,58 @@ impl ProgressBars {
 
 struct DropDowns {
     data: String,
+    password: String,
     value: i32,
+    value_history: Undoer<i32>,
+    checked: bool,
+    size: Size,
+    dark: bool,
+    volume: f32,
+    frequency: f64,
+    level: u8,
+    count: i32,
+    scale: f32,
+    fruit: usize,
+}
+
+const FRUITS: [&str; 12] = [
+    "Apple",
+    "Banana",
+    "Cherry",
+    "Date",
+    "Elderberry",
+    "Fig",
+    "Grape",
+    "Honeydew",
+    "Kiwi",
+    "Lemon",
+    "Mango",
+    "Pineapple",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Size {
+    Small,
+    Medium,
+    Large,
 }
 
 impl DropDowns {
     pub fn new() -> Self {
         Self {
             data: String::new(),
+            password: String::new(),
             value: 0,
+            value_history: Undoer::new(),
+            checked: false,
+            size: Size::Medium,
+            dark: true,
+            volume: 0.5,
+            frequency: 440.0,
+            level: 3,
+            count: 7,
+            scale: 1.0,
+            fruit: 0,
         }
     }
 
@@ -463,22 +902,21 @@ impl DropDowns {
         ui.horizontal(|ui| {
             ui.vertical(|ui| {
                 ui.drop_down("Drop Down", |ui| {
-                    self.data.push_str(&ui.ctx().input().keyboard.frame_input);
-                    if ui
-                        .ctx()
-                        .input()
-                        .keyboard
-                        .pressed
-                        .get(&crossterm::event::KeyCode::Backspace)
-                        .is_some()
-                    {
-                        self.data.pop();
-                    }
-
                     ui.label("input");
+                    TextEdit::new(&mut self.data)
+                        .placeholder("type something")
+                        .show(ui);
+                    ui.label("password");
+                    TextEdit::new(&mut self.password)
+                        .password(true)
+                        .max_len(16)
+                        .show(ui);
                     ui.bordered(|ui| {
                         ui.label(self.data.as_str());
                     });
+                    if ui.button("Copy").clicked() {
+                        ui.ctx().copy_text(self.data.as_str());
+                    }
                 });
             });
             ui.add_space_primary_direction(1);
@@ -507,6 +945,150 @@ impl DropDowns {
             });
 
             ui.label(format!("value: {}", self.value));
+
+            // ctrl+z/ctrl+y reach the app when no text field used them
+            let ctx = ui.ctx().clone();
+            ui.horizontal(|ui| {
+                let shortcuts = ctx.shortcuts().borrow().clone();
+                if ui.button("Undo").clicked() || ctx.consume_shortcut(&shortcuts.undo) {
+                    if let Some(value) = self.value_history.undo(&self.value) {
+                        self.value = value;
+                    }
+                }
+                ui.add_space_primary_direction(1);
+                if ui.button("Redo").clicked() || ctx.consume_shortcut(&shortcuts.redo) {
+                    if let Some(value) = self.value_history.redo(&self.value) {
+                        self.value = value;
+                    }
+                }
+            });
+            self.value_history.feed(&self.value);
+        });
+        ui.drop_down("Options", |ui| {
+            ui.checkbox(&mut self.checked, "Enable the thing");
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.size, Size::Small, "Small");
+                ui.add_space_primary_direction(1);
+                ui.radio_value(&mut self.size, Size::Medium, "Medium");
+                ui.add_space_primary_direction(1);
+                ui.radio_value(&mut self.size, Size::Large, "Large");
+            });
+            Toggle::new(&mut self.dark).text("Dark mode").show(ui);
+            Slider::new(&mut self.volume, 0.0..=1.0)
+                .text("volume")
+                .custom_formatter(|v| format!("{:.0}%", v * 100.0))
+                .show(ui);
+            Slider::new(&mut self.frequency, 20.0..=20000.0)
+                .logarithmic(true)
+                .decimals(0)
+                .text("Hz")
+                .show(ui);
+            ui.horizontal(|ui| {
+                Slider::new(&mut self.level, 0..=10)
+                    .vertical()
+                    .length(5)
+                    .show(ui);
+                ui.add_space_primary_direction(2);
+                ui.vertical(|ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("count ");
+                        ui.drag_value(&mut self.count);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("scale ");
+                        DragValue::new(&mut self.scale)
+                            .range(0.1..=10.0)
+                            .speed(0.05)
+                            .decimals(2)
+                            .suffix("x")
+                            .show(ui);
+                    });
+                });
+            });
+            ui.horizontal(|ui| {
+                ui.label("fruit ");
+                ComboBox::new("fruit")
+                    .max_rows(5)
+                    .show_index(ui, &mut self.fruit, &FRUITS);
+                ui.add_space_primary_direction(1);
+                ComboBox::new("size").show_values(
+                    ui,
+                    &mut self.size,
+                    &[
+                        (Size::Small, "Small"),
+                        (Size::Medium, "Medium"),
+                        (Size::Large, "Large"),
+                    ],
+                );
+            });
+            ui.label(format!(
+                "checked: {}, size: {:?}, dark: {}, fruit: {}",
+                self.checked, self.size, self.dark, FRUITS[self.fruit]
+            ));
         })
     }
 }
+
+struct Kanban {
+    columns: Vec<(&'static str, Vec<String>)>,
+}
+
+impl Kanban {
+    pub fn new() -> Self {
+        Self {
+            columns: vec![
+                (
+                    "Todo",
+                    vec!["Write docs".into(), "Fix resize".into(), "Tables".into()],
+                ),
+                ("Doing", vec!["Drag and drop".into()]),
+                ("Done", vec!["Spinner".into(), "Progress bar".into()]),
+            ],
+        }
+    }
+
+    fn ui(&mut self, ui: &mut etui::ui::Ui) {
+        ui.label("Drag cards between columns or within one to reorder them (esc cancels)");
+        ui.add_space_primary_direction(1);
+
+        let mut moved = None;
+        ui.horizontal(|ui| {
+            for (column_index, (title, cards)) in self.columns.iter().enumerate() {
+                let column = ui.vertical(|ui| {
+                    ui.label(*title);
+                    for (card_index, card) in cards.iter().enumerate() {
+                        let res = ui.dnd_drag_source(
+                            ("card", column_index, card_index),
+                            (column_index, card_index),
+                            |ui| {
+                                ui.bordered(|ui| ui.label(card.as_str()));
+                            },
+                        );
+                        // dropping onto a card inserts before it
+                        if let Some(from) =
+                            res.response.dnd_release_payload::<(usize, usize)>(ui.ctx())
+                        {
+                            moved = Some((*from, (column_index, card_index)));
+                        }
+                    }
+                    ui.set_minimum_size(etui::math_util::VecI2::new(20, ui.get_max().height));
+                    ui.get_current()
+                });
+
+                let response = ui.interact(etui::id::Id::new(("column", column_index)), column);
+                if let Some(from) = response.dnd_release_payload::<(usize, usize)>(ui.ctx()) {
+                    moved = moved.or(Some((*from, (column_index, cards.len()))));
+                }
+                ui.add_space_primary_direction(1);
+            }
+        });
+
+        if let Some(((from_column, from_card), (to_column, mut to_card))) = moved {
+            let card = self.columns[from_column].1.remove(from_card);
+            if from_column == to_column && from_card < to_card {
+                to_card -= 1;
+            }
+            self.columns[to_column].1.insert(to_card, card);
+        }
+    }
+}
@@ -0,0 +1,101 @@
+/// The clipboard shared by every widget.
+///
+/// Copied text is always kept in process and, when enabled, also sent to the
+/// terminal with the OSC 52 escape sequence so it lands in the system clipboard,
+/// this works over ssh as the terminal on the other end does the copying.
+/// Reading the system clipboard back is not widely allowed by terminals, text
+/// pasted through the terminal arrives as a bracketed paste instead. Under tmux
+/// the sequence is only forwarded with `set-clipboard on`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Clipboard {
+    contents: String,
+    osc52: bool,
+    pending: Option<String>,
+}
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clipboard {
+    /// Guesses from the environment whether the terminal understands OSC 52
+    pub fn new() -> Self {
+        let term = std::env::var("TERM").unwrap_or_default();
+        Self {
+            contents: String::new(),
+            osc52: !matches!(term.as_str(), "" | "dumb" | "linux"),
+            pending: None,
+        }
+    }
+
+    /// Only keeps copied text in process
+    pub fn local() -> Self {
+        Self {
+            osc52: false,
+            ..Self::new()
+        }
+    }
+
+    pub fn osc52(&self) -> bool {
+        self.osc52
+    }
+
+    pub fn set_osc52(&mut self, osc52: bool) {
+        self.osc52 = osc52;
+    }
+
+    pub fn set(&mut self, text: impl Into<String>) {
+        self.contents = text.into();
+        if self.osc52 {
+            self.pending = Some(osc52_sequence(&self.contents));
+        }
+    }
+
+    pub fn get(&self) -> &str {
+        &self.contents
+    }
+
+    /// The escape sequence to write to the terminal this frame, if something was copied
+    pub(crate) fn take_pending(&mut self) -> Option<String> {
+        self.pending.take()
+    }
+}
+
+/// `\x1b]52;c;<base64>\x07`
+pub fn osc52_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))
+}
+
+pub fn base64_encode(bytes: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(TABLE[(n >> (18 - i * 6)) as usize & 63] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[test]
+pub fn test_base64() {
+    assert_eq!(base64_encode(b""), "");
+    assert_eq!(base64_encode(b"f"), "Zg==");
+    assert_eq!(base64_encode(b"fo"), "Zm8=");
+    assert_eq!(base64_encode(b"foo"), "Zm9v");
+    assert_eq!(base64_encode("héllo".as_bytes()), "aMOpbGxv");
+    assert_eq!(osc52_sequence("hi"), "\x1b]52;c;aGk=\x07");
+}
@@ -0,0 +1,97 @@
+use std::{any::Any, hash::Hash};
+
+use crate::{
+    id::Id,
+    input::mouse::MouseButtonState,
+    layers,
+    math_util::{Rect, VecI2},
+    response::Response,
+    ui::{Layout, Ui},
+};
+
+/// Makes its contents draggable, while dragged the contents are drawn following
+/// the mouse and the payload can be picked up with [`Response::dnd_release_payload`]
+#[derive(Debug, Clone, Copy)]
+pub struct DragSource {
+    id_source: Id,
+}
+
+impl DragSource {
+    pub fn new(source: impl Hash) -> Self {
+        Self {
+            id_source: Id::new(source),
+        }
+    }
+
+    /// The id within `ui`, so the same source in another ui is another widget
+    pub fn id(&self, ui: &Ui) -> Id {
+        ui.id().with(self.id_source)
+    }
+
+    pub fn is_dragged(&self, ui: &Ui) -> bool {
+        ui.ctx().dnd_source() == Some(self.id(ui))
+    }
+
+    pub fn show<T: Any, R>(
+        self,
+        ui: &mut Ui,
+        payload: T,
+        func: impl FnOnce(&mut Ui) -> R,
+    ) -> DragSourceResponse<R> {
+        let ctx = ui.ctx().clone();
+        let id = self.id(ui);
+
+        if self.is_dragged(ui)
+            && ctx
+                .input()
+                .keyboard
+                .pressed
+                .contains_key(&crossterm::event::KeyCode::Esc)
+        {
+            ctx.dnd_clear();
+        }
+
+        if let (true, Some(position)) = (self.is_dragged(ui), ctx.input().mouse.position) {
+            // leave a gap where the contents were so the layout doesn't jump around
+            let size = ctx.get_memory_or(id, VecI2::new(0, 0));
+            let placeholder = ui.allocate_size(size);
+            let response = ui.interact(id, placeholder);
+
+            let mut preview = Ui::new(
+                ctx.clone(),
+                Layout::TopLeftVertical,
+                id.with("preview"),
+                Rect::new_pos_pos(position, ctx.screen_rect().bottom_right()),
+                layers::DRAG,
+            );
+            let inner_return = func(&mut preview);
+
+            return DragSourceResponse {
+                response,
+                inner_return,
+            };
+        }
+
+        let (inner_return, used) = ui.with_layout(ui.layout(), |ui| (func(ui), ui.get_current()));
+        ctx.insert_into_memory(id, used.size());
+        let response = ui.interact(id, used);
+
+        // the contents might have captured the mouse so check where the drag started ourselves
+        let mouse = &ctx.input().mouse;
+        if let MouseButtonState::Drag { start, .. } = mouse.buttons[0] {
+            if mouse.drag_started[0] && used.contains(start) {
+                ctx.dnd_set_payload(id, payload);
+            }
+        }
+
+        DragSourceResponse {
+            response,
+            inner_return,
+        }
+    }
+}
+
+pub struct DragSourceResponse<R> {
+    pub response: Response,
+    pub inner_return: R,
+}
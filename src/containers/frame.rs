@@ -3,17 +3,27 @@ use std::num::NonZeroU8;
 use crate::{
     context::Context,
     id::Id,
+    layers,
     ui::{Layout, Ui},
 };
 
-#[derive(Default)]
 pub struct Frame {
     layout: Option<Layout>,
+    layer: NonZeroU8,
+}
+
+impl Default for Frame {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Frame {
     pub fn new() -> Self {
-        Self { layout: None }
+        Self {
+            layout: None,
+            layer: layers::FRAME,
+        }
     }
 
     pub fn show<F: FnOnce(&mut Ui) -> R, R>(self, ctx: &Context, func: F) -> R {
@@ -23,7 +33,7 @@ impl Frame {
             layout,
             Id::new("frame"),
             ctx.screen_rect(),
-            NonZeroU8::new(128).unwrap(),
+            self.layer,
         ))
     }
 
@@ -31,4 +41,10 @@ impl Frame {
         self.layout = Some(layout);
         self
     }
+
+    /// Layer everything in the frame is drawn on, defaults to [`layers::FRAME`]
+    pub fn layer(mut self, layer: NonZeroU8) -> Self {
+        self.layer = layer;
+        self
+    }
 }
@@ -0,0 +1,238 @@
+use crate::{
+    id::Id,
+    layout::Align,
+    math_util::{Rect, VecI2},
+    ui::{Layout, Ui},
+};
+
+/// Sizes measured while showing a grid, used to place it the next frame
+#[derive(Debug, Clone, Default, PartialEq)]
+struct GridState {
+    /// widest cell of every column
+    widths: Vec<u16>,
+    /// tallest cell of every row
+    heights: Vec<u16>,
+    /// width of every cell by row, for aligning them
+    cells: Vec<Vec<u16>>,
+}
+
+/// Places widgets in rows and columns. Columns are as wide as their widest cell
+/// was the frame before, so labels and inputs of a form line up.
+pub struct Grid {
+    id_source: Id,
+    spacing: VecI2,
+    striped: bool,
+    align: Vec<Align>,
+}
+
+impl Grid {
+    pub fn new(id_source: impl std::hash::Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            spacing: VecI2::new(1, 0),
+            striped: false,
+            align: Vec::new(),
+        }
+    }
+
+    /// Empty cells between columns and rows
+    pub fn spacing(mut self, spacing: VecI2) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Give every other row the stripe background
+    pub fn striped(mut self, striped: bool) -> Self {
+        self.striped = striped;
+        self
+    }
+
+    /// How cells narrower than `column` are aligned in it
+    pub fn align(mut self, column: usize, align: Align) -> Self {
+        if self.align.len() <= column {
+            self.align.resize(column + 1, Align::Start);
+        }
+        self.align[column] = align;
+        self
+    }
+
+    pub fn show<R>(self, ui: &mut Ui, func: impl FnOnce(&mut GridUi) -> R) -> R {
+        let ctx = ui.ctx().clone();
+        let id = ui.id().with(self.id_source);
+        let last = ctx.get_memory_or(id, GridState::default());
+        let origin = ui.get_max().top_left();
+
+        let mut grid = GridUi {
+            ui,
+            grid: &self,
+            origin,
+            last,
+            next: GridState::default(),
+            spans: Vec::new(),
+            row: 0,
+            column: 0,
+            y: origin.y,
+            row_height: 0,
+        };
+        let inner = func(&mut grid);
+        if grid.column > 0 {
+            grid.end_row();
+        }
+
+        let GridUi {
+            ui,
+            last,
+            mut next,
+            spans,
+            y,
+            ..
+        } = grid;
+        widen_for_spans(&mut next.widths, &spans, self.spacing.x);
+        let size = VecI2::new(
+            total_width(&next.widths, self.spacing.x),
+            (y - origin.y).saturating_sub(self.spacing.y),
+        );
+        let _ = ui.allocate_area(Rect::new_pos_size(origin, size));
+
+        if next != last {
+            ctx.request_redraw();
+        }
+        ctx.insert_into_memory(id, next);
+        inner
+    }
+}
+
+/// Adds cells to a [`Grid`], row by row
+pub struct GridUi<'a> {
+    ui: &'a mut Ui,
+    grid: &'a Grid,
+    origin: VecI2,
+    last: GridState,
+    next: GridState,
+    /// column, span and width of cells spanning more than one column
+    spans: Vec<(usize, usize, u16)>,
+    row: usize,
+    column: usize,
+    y: u16,
+    row_height: u16,
+}
+
+impl GridUi<'_> {
+    /// The next cell of the current row
+    pub fn cell<R>(&mut self, func: impl FnOnce(&mut Ui) -> R) -> R {
+        self.cell_span(1, func)
+    }
+
+    /// A cell taking up `span` columns
+    pub fn cell_span<R>(&mut self, span: usize, func: impl FnOnce(&mut Ui) -> R) -> R {
+        let span = span.max(1);
+        let spacing = self.grid.spacing;
+        if self.column == 0 && self.grid.striped && self.row % 2 == 1 {
+            self.draw_stripe();
+        }
+
+        let widths = &self.last.widths;
+        let width_of = |column: usize| widths.get(column).copied().unwrap_or(0);
+        let x = (0..self.column).fold(self.origin.x, |x, column| {
+            x.saturating_add(width_of(column)).saturating_add(spacing.x)
+        });
+        let space = (self.column..self.column + span)
+            .fold(0u16, |space, column| space.saturating_add(width_of(column)))
+            .saturating_add(spacing.x.saturating_mul(span as u16 - 1));
+        let index = self.next.cells.get(self.row).map_or(0, Vec::len);
+        let last_width = self
+            .last
+            .cells
+            .get(self.row)
+            .and_then(|cells| cells.get(index))
+            .copied()
+            .unwrap_or(0);
+        let align = self
+            .grid
+            .align
+            .get(self.column)
+            .copied()
+            .unwrap_or_default();
+        let x = x.saturating_add(align.offset(space, last_width));
+
+        // cells can grow past their column, it gets wider the next frame
+        let max = self.ui.get_max();
+        let rect = Rect::new_pos_pos(VecI2::new(x, self.y), max.bottom_right());
+        let mut child = self.ui.child_ui(rect, Layout::TopLeftHorizontal);
+        let inner = func(&mut child);
+        let size = child.get_current().size();
+
+        if self.next.cells.len() <= self.row {
+            self.next.cells.resize(self.row + 1, Vec::new());
+        }
+        self.next.cells[self.row].push(size.x);
+        if self.next.widths.len() < self.column + span {
+            self.next.widths.resize(self.column + span, 0);
+        }
+        if span == 1 {
+            self.next.widths[self.column] = self.next.widths[self.column].max(size.x);
+        } else {
+            self.spans.push((self.column, span, size.x));
+        }
+        self.row_height = self.row_height.max(size.y);
+        self.column += span;
+        inner
+    }
+
+    /// Starts the next row
+    pub fn end_row(&mut self) {
+        self.next.heights.push(self.row_height);
+        self.y = self
+            .y
+            .saturating_add(self.row_height)
+            .saturating_add(self.grid.spacing.y);
+        self.row += 1;
+        self.column = 0;
+        self.row_height = 0;
+    }
+
+    fn draw_stripe(&mut self) {
+        let style = self.ui.ctx().style().borrow().stripe;
+        let height = self.last.heights.get(self.row).copied().unwrap_or(0);
+        let width = total_width(&self.last.widths, self.grid.spacing.x);
+        let rect = Rect::new_pos_size(VecI2::new(self.origin.x, self.y), VecI2::new(width, height));
+        let blank = " ".repeat(width as usize);
+        for y in rect.y..rect.y + rect.height {
+            self.ui.draw(&blank, style, VecI2::new(rect.x, y), rect);
+        }
+    }
+}
+
+fn total_width(widths: &[u16], spacing: u16) -> u16 {
+    let gaps = spacing.saturating_mul(widths.len().saturating_sub(1) as u16);
+    widths
+        .iter()
+        .fold(gaps, |total, &width| total.saturating_add(width))
+}
+
+/// Widens the last column a spanning cell covers when the cell does not fit
+pub(crate) fn widen_for_spans(widths: &mut [u16], spans: &[(usize, usize, u16)], spacing: u16) {
+    for &(column, span, width) in spans {
+        let covered = &widths[column..column + span];
+        let space = total_width(covered, spacing);
+        if let Some(last) = widths.get_mut(column + span - 1) {
+            *last += width.saturating_sub(space);
+        }
+    }
+}
+
+#[test]
+pub fn test_grid_spans() {
+    let mut widths = vec![4, 6, 0];
+    // fits in the two columns and the gap between them
+    widen_for_spans(&mut widths, &[(0, 2, 11)], 1);
+    assert_eq!(widths, vec![4, 6, 0]);
+    widen_for_spans(&mut widths, &[(0, 2, 14)], 1);
+    assert_eq!(widths, vec![4, 9, 0]);
+    widen_for_spans(&mut widths, &[(1, 2, 15)], 2);
+    assert_eq!(widths, vec![4, 9, 4]);
+
+    assert_eq!(Align::End.offset(10, 4), 6);
+    assert_eq!(Align::Center.offset(10, 4), 3);
+    assert_eq!(Align::Center.offset(3, 4), 0);
+}
@@ -0,0 +1,589 @@
+use std::{
+    ops::Range,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::{
+    context::{Cursor, FocusLock},
+    id::Id,
+    input::mouse::MouseButtonState,
+    math_util::{Rect, VecI2},
+    response::Response,
+    ui::{Layout, Ui},
+};
+
+use super::scroll_area::{drag_scrollbar, draw_scrollbar};
+
+/// Wheel notches are multiplied by this
+const WHEEL_STEP: isize = 3;
+
+/// A second click on the same row within this time activates it
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+/// Selected rows kept as sorted, non overlapping ranges so selecting millions
+/// of rows at once stays cheap
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Selection {
+    ranges: Vec<Range<usize>>,
+}
+
+impl Selection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn contains(&self, row: usize) -> bool {
+        let i = self.ranges.partition_point(|range| range.end <= row);
+        self.ranges.get(i).is_some_and(|range| range.start <= row)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of selected rows
+    pub fn len(&self) -> usize {
+        self.ranges.iter().map(|range| range.len()).sum()
+    }
+
+    pub fn ranges(&self) -> &[Range<usize>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.ranges.iter().flat_map(|range| range.clone())
+    }
+
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+    }
+
+    pub fn select(&mut self, rows: Range<usize>) {
+        if rows.is_empty() {
+            return;
+        }
+        let (mut start, mut end) = (rows.start, rows.end);
+        // merge everything overlapping or touching the new range into it
+        self.ranges.retain(|range| {
+            if range.end < start || range.start > end {
+                true
+            } else {
+                start = start.min(range.start);
+                end = end.max(range.end);
+                false
+            }
+        });
+        let at = self.ranges.partition_point(|range| range.start < start);
+        self.ranges.insert(at, start..end);
+    }
+
+    pub fn deselect(&mut self, rows: Range<usize>) {
+        let mut ranges = Vec::with_capacity(self.ranges.len() + 1);
+        for range in self.ranges.drain(..) {
+            if range.end <= rows.start || range.start >= rows.end {
+                ranges.push(range);
+                continue;
+            }
+            if range.start < rows.start {
+                ranges.push(range.start..rows.start);
+            }
+            if range.end > rows.end {
+                ranges.push(rows.end..range.end);
+            }
+        }
+        self.ranges = ranges;
+    }
+
+    pub fn toggle(&mut self, row: usize) {
+        if self.contains(row) {
+            self.deselect(row..row + 1);
+        } else {
+            self.select(row..row + 1);
+        }
+    }
+
+    /// Selects only `row`
+    pub fn set(&mut self, row: usize) {
+        self.ranges.clear();
+        self.ranges.push(row..row + 1);
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionMode {
+    None,
+    #[default]
+    Single,
+    /// shift extends the selection, ctrl toggles rows and moves without selecting
+    Multiple,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ListViewState {
+    /// first row shown
+    pub scroll: usize,
+    /// the row moved by the keyboard
+    pub cursor: usize,
+    /// where shift selections start from
+    pub anchor: usize,
+    pub selection: Selection,
+    /// the last row was in view last frame
+    pub at_bottom: bool,
+    /// the cell of the thumb grabbed while dragging the scrollbar
+    pub grab: u16,
+}
+
+impl ListViewState {
+    /// Moves the keyboard cursor, selecting the way `mode` asks for. `extend` grows
+    /// the selection from the anchor, `keep` leaves the selection as it is
+    pub(crate) fn move_cursor(
+        &mut self,
+        mode: SelectionMode,
+        row: usize,
+        extend: bool,
+        keep: bool,
+    ) {
+        self.cursor = row;
+        match mode {
+            SelectionMode::None => self.anchor = row,
+            SelectionMode::Single => {
+                self.anchor = row;
+                self.selection.set(row);
+            }
+            SelectionMode::Multiple if extend => {
+                let (start, end) = (self.anchor.min(row), self.anchor.max(row));
+                self.selection.clear();
+                self.selection.select(start..end + 1);
+            }
+            SelectionMode::Multiple => {
+                self.anchor = row;
+                if !keep {
+                    self.selection.set(row);
+                }
+            }
+        }
+    }
+}
+
+/// What a row is shown as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListRow {
+    pub index: usize,
+    pub selected: bool,
+    /// the keyboard cursor is on this row
+    pub cursor: bool,
+}
+
+pub struct ListViewResponse {
+    pub response: Response,
+    pub selection: Selection,
+    pub cursor: usize,
+    /// row enter was pressed on or that was double clicked
+    pub activated: Option<usize>,
+}
+
+enum RowHeights<'a> {
+    Uniform(u16),
+    Variable(Box<dyn Fn(usize) -> u16 + 'a>),
+}
+
+/// A list that only lays out and draws the rows in view, so it works the same
+/// for a handful of rows and for millions. The whole list is a single focusable
+/// widget, rows are chosen with the arrow keys, page up/down, home/end and the mouse
+pub struct ListView<'a> {
+    id_source: Id,
+    rows: usize,
+    heights: RowHeights<'a>,
+    mode: SelectionMode,
+    stick_to_bottom: bool,
+    size: Option<VecI2>,
+}
+
+impl<'a> ListView<'a> {
+    pub fn new(id_source: impl std::hash::Hash, rows: usize) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            rows,
+            heights: RowHeights::Uniform(1),
+            mode: SelectionMode::default(),
+            stick_to_bottom: false,
+            size: None,
+        }
+    }
+
+    /// Every row is `height` cells tall
+    pub fn row_height(mut self, height: u16) -> Self {
+        self.heights = RowHeights::Uniform(height.max(1));
+        self
+    }
+
+    /// Rows have their own height, `height` is only called for rows near the view
+    pub fn row_heights(mut self, height: impl Fn(usize) -> u16 + 'a) -> Self {
+        self.heights = RowHeights::Variable(Box::new(height));
+        self
+    }
+
+    pub fn selection_mode(mut self, mode: SelectionMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Keeps showing the last rows as more are added while scrolled to the end
+    pub fn stick_to_bottom(mut self, stick: bool) -> Self {
+        self.stick_to_bottom = stick;
+        self
+    }
+
+    /// Defaults to all the space available
+    pub fn desired_size(mut self, size: VecI2) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// The id a list view made with `id_source` has within `ui`, its
+    /// [`ListViewState`] is kept in memory under it
+    pub fn id(ui: &Ui, id_source: impl std::hash::Hash) -> Id {
+        ui.id().with(Id::new(id_source))
+    }
+
+    fn height(&self, row: usize) -> u16 {
+        match &self.heights {
+            RowHeights::Uniform(height) => *height,
+            RowHeights::Variable(height) => height(row).max(1),
+        }
+    }
+
+    /// The first row to show so that `last` ends up at the bottom of `height` cells
+    fn first_for_bottom(&self, last: usize, height: u16) -> usize {
+        let mut used = 0u32;
+        let mut first = last + 1;
+        while first > 0 {
+            let row_height = self.height(first - 1) as u32;
+            // the last row is always shown even when it does not fit
+            if used + row_height > height as u32 && first <= last {
+                break;
+            }
+            used += row_height;
+            first -= 1;
+        }
+        first
+    }
+
+    /// How many rows starting at `first` fit in `height` cells, at least one
+    fn rows_fitting(&self, first: usize, height: u16) -> usize {
+        let mut used = 0u32;
+        let mut count = 0;
+        while first + count < self.rows {
+            used += self.height(first + count) as u32;
+            if used > height as u32 {
+                break;
+            }
+            count += 1;
+        }
+        count.max(1)
+    }
+
+    /// The row at `y` cells below the top when scrolled to `first`
+    fn row_at(&self, first: usize, y: u16) -> Option<usize> {
+        let mut top = 0u32;
+        (first..self.rows).find(|row| {
+            top += self.height(*row) as u32;
+            top > y as u32
+        })
+    }
+
+    pub fn show(self, ui: &mut Ui, mut show_row: impl FnMut(&mut Ui, ListRow)) -> ListViewResponse {
+        let ctx = ui.ctx().clone();
+        let id = ui.id().with(self.id_source);
+        let default_style = *ctx.style().borrow();
+        let mut state = ctx.get_memory_or(
+            id,
+            ListViewState {
+                at_bottom: self.stick_to_bottom,
+                ..Default::default()
+            },
+        );
+
+        let size = self.size.unwrap_or_else(|| ui.get_max().size());
+        let area = ui.allocate_size(size);
+        let last = self.rows.saturating_sub(1);
+        let max_scroll = if self.rows == 0 {
+            0
+        } else {
+            self.first_for_bottom(last, area.height)
+        };
+        let has_scrollbar = max_scroll > 0 && area.width > 1;
+        let rows_area = Rect::new_pos_size(
+            area.top_left(),
+            VecI2::new(area.width - has_scrollbar as u16, area.height),
+        );
+        let track = Rect::new_pos_size(
+            VecI2::new(rows_area.x + rows_area.width, area.y),
+            VecI2::new(1, area.height),
+        );
+
+        // rows can disappear between frames
+        state.cursor = state.cursor.min(last);
+        state.anchor = state.anchor.min(last);
+        state.selection.deselect(self.rows..usize::MAX);
+        let selection_before = state.selection.clone();
+
+        if self.stick_to_bottom && state.at_bottom {
+            state.scroll = max_scroll;
+        }
+
+        // the scrollbar gets the mouse before the rows under it
+        if has_scrollbar {
+            drag_scrollbar(
+                ui,
+                id.with("scrollbar"),
+                track,
+                true,
+                self.rows - max_scroll,
+                self.rows,
+                &mut state.scroll,
+                &mut state.grab,
+            );
+        }
+        state.scroll = state.scroll.min(max_scroll);
+        let (drawn_scroll, drawn_cursor) = (state.scroll, state.cursor);
+        let mut cursor_rect = None;
+
+        // only the rows in view are laid out
+        let mut y = area.y;
+        let bottom = area.y + area.height;
+        let mut row = state.scroll;
+        while row < self.rows && y < bottom {
+            let height = self.height(row).min(bottom - y);
+            let rect = Rect::new_pos_size(
+                VecI2::new(rows_area.x, y),
+                VecI2::new(rows_area.width, height),
+            );
+            let info = ListRow {
+                index: row,
+                selected: state.selection.contains(row),
+                cursor: row == state.cursor,
+            };
+            if info.selected {
+                let blank = " ".repeat(rect.width as usize);
+                for line in rect.y..rect.y + rect.height {
+                    ui.draw(
+                        &blank,
+                        default_style.list_highlight,
+                        VecI2::new(rect.x, line),
+                        rect,
+                    );
+                }
+            }
+            let mut row_ui = Ui::new(
+                ctx.clone(),
+                Layout::TopLeftHorizontal,
+                id.with(row),
+                rect,
+                ui.layer(),
+            );
+            // a zero offset only clips rows that are cut off at the bottom
+            ctx.scrolled(rect, VecI2::new(0, 0), || show_row(&mut row_ui, info));
+            if info.cursor {
+                cursor_rect = Some(rect);
+            }
+            y += height;
+            row += 1;
+        }
+
+        // interact after the rows so widgets inside them get the mouse first
+        let mut response = ui.interact_scroll(id, area);
+
+        if response.scrolled() {
+            let (_, dy) = response.scroll_delta();
+            state.scroll = state
+                .scroll
+                .saturating_add_signed(-dy as isize * WHEEL_STEP);
+        }
+
+        let mut follow_cursor = false;
+        let mut activated = None;
+        let mouse = ctx.input().mouse;
+        if let MouseButtonState::Down(pos) = mouse.buttons[0] {
+            if rows_area.contains(pos) && response.pressed() {
+                response.request_focus(&ctx);
+                if let Some(row) = self.row_at(state.scroll, pos.y - area.y) {
+                    let modifiers = mouse.modifiers;
+                    if self.mode == SelectionMode::Multiple
+                        && modifiers.contains(KeyModifiers::CONTROL)
+                    {
+                        state.selection.toggle(row);
+                        state.cursor = row;
+                        state.anchor = row;
+                    } else {
+                        let extend = modifiers.contains(KeyModifiers::SHIFT);
+                        state.move_cursor(self.mode, row, extend, false);
+                    }
+                    let now = Instant::now();
+                    let last_click: Option<(usize, Instant)> =
+                        ctx.get_memory_or(id.with("click"), None);
+                    if last_click.is_some_and(|(last, at)| last == row && now - at <= DOUBLE_CLICK)
+                    {
+                        activated = Some(row);
+                    }
+                    ctx.insert_into_memory(id.with("click"), Some((row, now)));
+                }
+            }
+        }
+
+        if response.focused() && self.rows > 0 {
+            ctx.lock_focus(FocusLock {
+                vertical: true,
+                ..Default::default()
+            });
+            let page = self.rows_fitting(state.scroll, area.height);
+            for key in &ctx.input().keyboard.events {
+                let extend = key.modifiers.contains(KeyModifiers::SHIFT);
+                let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+                let row = match key.code {
+                    KeyCode::Up => state.cursor.saturating_sub(1),
+                    KeyCode::Down => (state.cursor + 1).min(last),
+                    KeyCode::PageUp => state.cursor.saturating_sub(page),
+                    KeyCode::PageDown => (state.cursor + page).min(last),
+                    KeyCode::Home => 0,
+                    KeyCode::End => last,
+                    KeyCode::Enter => {
+                        activated = Some(state.cursor);
+                        continue;
+                    }
+                    KeyCode::Char(' ') => {
+                        match self.mode {
+                            SelectionMode::None => {}
+                            SelectionMode::Single => state.selection.set(state.cursor),
+                            SelectionMode::Multiple => state.selection.toggle(state.cursor),
+                        }
+                        state.anchor = state.cursor;
+                        continue;
+                    }
+                    KeyCode::Char('a') if ctrl && self.mode == SelectionMode::Multiple => {
+                        state.selection.select(0..self.rows);
+                        continue;
+                    }
+                    _ => continue,
+                };
+                state.move_cursor(self.mode, row, extend, ctrl);
+                follow_cursor = true;
+            }
+        }
+
+        // keep the cursor in view after it moved
+        if follow_cursor {
+            if state.cursor < state.scroll {
+                state.scroll = state.cursor;
+            } else {
+                state.scroll = state
+                    .scroll
+                    .max(self.first_for_bottom(state.cursor, area.height));
+            }
+        }
+
+        state.scroll = state.scroll.min(max_scroll);
+        state.at_bottom = state.scroll >= max_scroll;
+
+        if let Some(rect) = cursor_rect.filter(|_| response.focused()) {
+            ctx.set_cursor(Cursor {
+                x: rect.x,
+                y: rect.y,
+            });
+        }
+
+        if has_scrollbar {
+            draw_scrollbar(
+                ui,
+                track,
+                true,
+                self.rows - max_scroll,
+                self.rows,
+                state.scroll,
+            );
+        }
+
+        if state.selection != selection_before {
+            response.mark_changed();
+        }
+        // the rows were already drawn before the mouse and keys were handled
+        if state.selection != selection_before
+            || state.scroll != drawn_scroll
+            || state.cursor != drawn_cursor
+        {
+            ctx.request_redraw();
+        }
+        let list_response = ListViewResponse {
+            response,
+            selection: state.selection.clone(),
+            cursor: state.cursor,
+            activated,
+        };
+        ctx.insert_into_memory(id, state);
+        list_response
+    }
+}
+
+#[test]
+pub fn test_selection_ranges() {
+    let mut selection = Selection::new();
+    selection.select(10..20);
+    selection.select(30..40);
+    assert_eq!(selection.ranges(), &[10..20, 30..40]);
+
+    // touching and overlapping ranges merge
+    selection.select(20..30);
+    assert_eq!(selection.ranges().len(), 1);
+    assert_eq!(selection.ranges()[0], 10..40);
+    selection.select(0..1);
+    selection.select(5..12);
+    assert_eq!(selection.ranges(), &[0..1, 5..40]);
+
+    selection.deselect(15..20);
+    assert_eq!(selection.ranges(), &[0..1, 5..15, 20..40]);
+    assert!(selection.contains(14) && !selection.contains(15) && selection.contains(20));
+    assert_eq!(selection.len(), 1 + 10 + 20);
+
+    selection.toggle(0);
+    selection.toggle(15);
+    assert_eq!(selection.ranges(), &[5..16, 20..40]);
+
+    // a huge selection stays small
+    selection.select(0..10_000_000);
+    assert_eq!(selection.ranges().len(), 1);
+    assert_eq!(selection.len(), 10_000_000);
+    assert_eq!(selection.iter().take(3).collect::<Vec<_>>(), vec![0, 1, 2]);
+}
+
+#[test]
+pub fn test_row_widgets_get_clicks() {
+    use crate::context::{Context, ContextInner};
+    use crossterm::event::{Event, MouseButton, MouseEvent, MouseEventKind};
+
+    let mut inner = ContextInner::new(VecI2::new(20, 5));
+    let ctx = unsafe { Context::new(&mut inner) };
+    let down = Event::Mouse(MouseEvent {
+        kind: MouseEventKind::Down(MouseButton::Left),
+        column: 1,
+        row: 1,
+        modifiers: KeyModifiers::NONE,
+    });
+    ctx.inner_mut().unwrap().handle_event(down);
+    ctx.inner_mut().unwrap().start_frame();
+
+    // the button in the second row gets the click, not the list around it
+    let mut clicked = None;
+    let mut list_pressed = true;
+    ctx.frame(|ui| {
+        let list = ListView::new("list", 3).show(ui, |ui, row| {
+            if ui.button("ok").clicked() {
+                clicked = Some(row.index);
+            }
+        });
+        list_pressed = list.response.pressed();
+    });
+    assert_eq!(clicked, Some(1));
+    assert!(!list_pressed);
+}
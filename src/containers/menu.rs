@@ -0,0 +1,357 @@
+use std::ops::Range;
+
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::{
+    context::{Context, FocusLock},
+    id::Id,
+    input::{
+        mouse::{mouse_buttons, MouseButtonState},
+        shortcut::Shortcut,
+    },
+    layers,
+    math_util::{Rect, VecI2},
+    response::Response,
+    style::Style,
+    ui::{Layout, Ui},
+    widgets::text_edit::str_width,
+};
+
+use super::popup::{draw_border, fill_box, place_near, Side};
+
+/// The menu that is open, there is only ever one
+#[derive(Clone, Debug)]
+struct OpenMenu {
+    /// the widget it was opened from
+    owner: Id,
+    /// screen area the menu opens below
+    anchor: Rect,
+    /// the click that opened the menu does not close it again
+    opened: usize,
+    /// where the owner is if it had focus, it gets it back once the menu closes
+    refocus: Option<Rect>,
+    /// open submenus, the one on the top menu first
+    path: Vec<Id>,
+    /// depth, area and focus order positions of every menu shown this frame
+    levels: Vec<(usize, Rect, Range<usize>)>,
+}
+
+fn open_menu_id() -> Id {
+    Id::new("open menu")
+}
+
+fn current(ctx: &Context) -> Option<OpenMenu> {
+    ctx.get_memory_or(open_menu_id(), None)
+}
+
+fn set_current(ctx: &Context, menu: Option<OpenMenu>) {
+    ctx.insert_into_memory(open_menu_id(), menu);
+}
+
+/// Opens the menu of `owner` below `anchor` in screen coordinates, closing any
+/// other menu. `refocus` is where the owner is if focus should go back to it
+pub(crate) fn open_menu(ctx: &Context, owner: Id, anchor: Rect, refocus: Option<Rect>) {
+    set_current(
+        ctx,
+        Some(OpenMenu {
+            owner,
+            anchor,
+            opened: ctx.get_frame(),
+            refocus,
+            path: Vec::new(),
+            levels: Vec::new(),
+        }),
+    );
+    ctx.request_redraw();
+}
+
+/// Closes the open menu, if any
+pub fn close_menu(ctx: &Context) {
+    if let Some(menu) = current(ctx) {
+        if let Some(rect) = menu.refocus {
+            ctx.request_focus(menu.owner, rect);
+        }
+        set_current(ctx, None);
+        ctx.request_redraw();
+    }
+}
+
+/// Whether the menu opened from `owner` is open
+pub fn is_menu_open(ctx: &Context, owner: Id) -> bool {
+    current(ctx).is_some_and(|menu| menu.owner == owner)
+}
+
+/// Shows `func` in the menu of `owner` if it is open, returns whether it still is
+pub(crate) fn show_menu(ctx: &Context, owner: Id, func: impl FnOnce(&mut Ui)) -> bool {
+    let Some(mut menu) = current(ctx).filter(|menu| menu.owner == owner) else {
+        return false;
+    };
+    menu.levels.clear();
+    let anchor = menu.anchor;
+    let opened = menu.opened;
+    set_current(ctx, Some(menu));
+
+    ctx.unscrolled(|| {
+        show_level(ctx, owner.with("menu"), anchor, false, 0, func);
+        // an item may have closed it
+        let Some(menu) = current(ctx).filter(|menu| menu.owner == owner) else {
+            return false;
+        };
+
+        let clicked_outside = opened != ctx.get_frame()
+            && ctx.input().mouse.buttons.iter().any(|button| {
+                matches!(button, MouseButtonState::Down(pos)
+                    if !menu.levels.iter().any(|(_, rect, _)| rect.contains(*pos)))
+            });
+        let escape = ctx.consume_shortcut(&[Shortcut::new(KeyModifiers::NONE, KeyCode::Esc)]);
+        if clicked_outside || escape {
+            close_menu(ctx);
+            return false;
+        }
+
+        // up, down and tab move between the entries of the innermost menu,
+        // left and right open and close submenus
+        if let Some((_, _, range)) = menu.levels.iter().max_by_key(|(depth, ..)| *depth) {
+            ctx.trap_focus(range.clone());
+        }
+        ctx.lock_focus(FocusLock {
+            horizontal: true,
+            vertical: false,
+            tab: false,
+        });
+        true
+    })
+}
+
+/// Opens `func` as a menu where `response` was right clicked, or below it when
+/// the context menu shortcut is pressed while it is focused
+pub(crate) fn context_menu(ctx: &Context, response: &Response, func: impl FnOnce(&mut Ui)) {
+    let rect = ctx.to_screen(response.rect);
+    let shortcuts = ctx.shortcuts().borrow().context_menu.clone();
+    let anchor = match response.buttons[mouse_buttons::SECONDARY] {
+        MouseButtonState::Down(pos) => {
+            Some(ctx.to_screen(Rect::new_pos_size(pos, VecI2::new(0, 0))))
+        }
+        _ if response.focused() && ctx.consume_shortcut(&shortcuts) => Some(rect),
+        _ => None,
+    };
+    if let Some(anchor) = anchor {
+        open_menu(ctx, response.id, anchor, response.focused().then_some(rect));
+    }
+    show_menu(ctx, response.id, func);
+}
+
+/// How many menus deep `ui` is, menus at each depth go on their own layer
+fn depth(ui: &Ui) -> usize {
+    ui.layer().get().saturating_sub(layers::MENU.get()) as usize
+}
+
+/// One bordered list of entries below `anchor`, or beside it for submenus.
+/// Returns the focus order positions of the entries
+fn show_level(
+    ctx: &Context,
+    id: Id,
+    anchor: Rect,
+    beside: bool,
+    depth: usize,
+    func: impl FnOnce(&mut Ui),
+) -> Range<usize> {
+    let screen = ctx.screen_rect();
+    let style = ctx.style().borrow().popup;
+    let layer = layers::MENU.saturating_add(depth.min(u8::MAX as usize) as u8);
+
+    let size = ctx.get_memory_or(id, VecI2::new(2, 2));
+    let side = if beside { Side::Beside } else { Side::Below };
+    let rect = place_near(anchor, size, screen, side);
+    ctx.register_layer_area(rect, layer);
+    fill_box(ctx, rect, layer, style);
+    draw_border(ctx, rect, layer, style);
+
+    let mut inner = rect;
+    inner.shrink_evenly(1);
+    let mut ui = Ui::new(
+        ctx.clone(),
+        Layout::TopLeftVertical,
+        id.with("entries"),
+        Rect::new_pos_pos(inner.top_left(), screen.bottom_right()),
+        layer,
+    );
+    // entries stretch to the widest one
+    ui.set_minimum_size(VecI2::new(inner.width, 0));
+    let first = ctx.focus().borrow().count();
+    func(&mut ui);
+    let last = ctx.focus().borrow().count();
+
+    let used = ui.get_current().size() + VecI2::new(2, 2);
+    if used != size {
+        ctx.request_redraw();
+    }
+    ctx.insert_into_memory(id, used);
+    if let Some(mut menu) = current(ctx) {
+        menu.levels.push((depth, rect, first..last));
+        set_current(ctx, Some(menu));
+    }
+    first..last
+}
+
+/// A row of a menu with `text` on the left and `hint` on the right. Also returns
+/// whether the mouse moved onto it this frame
+fn menu_row(ui: &mut Ui, text: &str, hint: &str, enabled: bool) -> (Response, bool) {
+    let ctx = ui.ctx().clone();
+    let default_style = *ctx.style().borrow();
+    let text_width = str_width(text, None);
+    let hint_width = str_width(hint, None);
+    let gap = if hint_width > 0 { 2 } else { 0 };
+    let width = (text_width + gap + hint_width + 2).max(ui.get_current().width);
+    let rect = ui.allocate_size(VecI2::new(width, 1));
+    let id = Id::new(ui.next_id_source());
+
+    // disabled entries can not be focused or clicked
+    let response = if enabled {
+        ui.interact(id, rect)
+    } else {
+        Response::new(rect, id, None)
+    };
+    let mouse = ctx.input().mouse;
+    let pointed = response.hovered()
+        && mouse.delta() != (0, 0)
+        && mouse.position.is_some_and(|pos| rect.contains(pos));
+    if pointed && !response.focused() {
+        response.request_focus(&ctx);
+    }
+
+    let style = if !enabled {
+        Style {
+            fg: default_style.placeholder.fg,
+            ..default_style.popup
+        }
+    } else if response.focused() || response.pressed() {
+        default_style.list_highlight
+    } else {
+        default_style.popup
+    };
+    let padding = width.saturating_sub(text_width + hint_width + 2) as usize;
+    let line = format!(" {text}{}{hint} ", " ".repeat(padding));
+    ui.draw(&line, style, rect.top_left(), rect);
+    (response, pointed)
+}
+
+/// Closes the submenus deeper than `depth`
+fn close_submenus(ctx: &Context, depth: usize) {
+    if let Some(mut menu) = current(ctx).filter(|menu| menu.path.len() > depth) {
+        menu.path.truncate(depth);
+        set_current(ctx, Some(menu));
+        ctx.request_redraw();
+    }
+}
+
+/// An entry of a menu that closes the menu once clicked
+pub struct MenuItem {
+    text: String,
+    shortcut: Option<String>,
+    enabled: bool,
+}
+
+impl MenuItem {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            shortcut: None,
+            enabled: true,
+        }
+    }
+
+    /// Shown on the right, the shortcut itself still has to be handled
+    pub fn shortcut(mut self, shortcut: impl Into<String>) -> Self {
+        self.shortcut = Some(shortcut.into());
+        self
+    }
+
+    /// Disabled entries are greyed out and can not be focused or clicked
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui) -> Response {
+        let hint = self.shortcut.as_deref().unwrap_or("");
+        let (response, pointed) = menu_row(ui, &self.text, hint, self.enabled);
+        if pointed {
+            close_submenus(ui.ctx(), depth(ui));
+        }
+        if response.clicked() {
+            close_menu(ui.ctx());
+        }
+        response
+    }
+}
+
+/// An entry of a menu that opens another menu beside it when hovered, clicked
+/// or on the right arrow. The left arrow goes back
+pub struct SubMenu {
+    text: String,
+    enabled: bool,
+}
+
+impl SubMenu {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            enabled: true,
+        }
+    }
+
+    /// Disabled entries are greyed out and can not be focused or opened
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui, func: impl FnOnce(&mut Ui)) -> Response {
+        let ctx = ui.ctx().clone();
+        let depth = depth(ui);
+        let arrow = ctx.style().borrow().pointers.right;
+        let (response, pointed) = menu_row(ui, &self.text, arrow, self.enabled);
+        let Some(mut menu) = current(&ctx) else {
+            return response;
+        };
+
+        let mut open = menu.path.get(depth) == Some(&response.id);
+        let right = response.focused()
+            && ctx.consume_shortcut(&[Shortcut::new(KeyModifiers::NONE, KeyCode::Right)]);
+        if self.enabled && !open && (pointed || response.clicked() || right) {
+            menu.path.truncate(depth);
+            menu.path.push(response.id);
+            set_current(&ctx, Some(menu));
+            ctx.request_redraw();
+            open = true;
+        }
+
+        if open {
+            // beside the border rather than the row
+            let mut anchor = response.rect;
+            anchor.x = anchor.x.saturating_sub(1);
+            anchor.width += 2;
+            let range = show_level(
+                &ctx,
+                response.id.with("menu"),
+                anchor,
+                true,
+                depth + 1,
+                func,
+            );
+            let focused_inside = ctx
+                .focus()
+                .borrow()
+                .focused_index()
+                .is_some_and(|index| range.contains(&index));
+            if focused_inside
+                && ctx.consume_shortcut(&[Shortcut::new(KeyModifiers::NONE, KeyCode::Left)])
+            {
+                close_submenus(&ctx, depth);
+                response.request_focus(&ctx);
+            }
+        }
+        response
+    }
+}
@@ -0,0 +1,182 @@
+use crossterm::{
+    event::{KeyCode, KeyModifiers},
+    style::Attribute,
+};
+
+use crate::{
+    context::FocusLock,
+    id::Id,
+    input::shortcut::Shortcut,
+    math_util::{Rect, VecI2},
+    response::Response,
+    ui::{Layout, Ui},
+    widgets::text_edit::str_width,
+};
+
+use super::menu::{close_menu, is_menu_open, open_menu, show_menu};
+
+/// A row of menu titles that open menus below them. F10 focuses the first
+/// title, alt and the underlined letter of a title opens its menu, and left and
+/// right go to the neighbouring menu while one is open.
+pub struct MenuBar {
+    id: Id,
+}
+
+impl Default for MenuBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MenuBar {
+    pub fn new() -> Self {
+        Self {
+            id: Id::new("menu bar"),
+        }
+    }
+
+    /// Use when there is more than one menu bar
+    pub fn id_source(mut self, id_source: impl std::hash::Hash) -> Self {
+        self.id = Id::new(id_source);
+        self
+    }
+
+    pub fn show<R>(self, ui: &mut Ui, func: impl FnOnce(&mut MenuBarUi) -> R) -> R {
+        let ctx = ui.ctx().clone();
+        let style = ctx.style().borrow().button;
+        let last_titles = ctx.get_memory_or(self.id, Vec::new());
+        let focus_first =
+            ctx.consume_shortcut(&[Shortcut::new(KeyModifiers::NONE, KeyCode::F(10))]);
+        if focus_first {
+            close_menu(&ctx);
+        }
+
+        let rect = ui.allocate_size(VecI2::new(ui.get_max().width, 1));
+        ui.draw(
+            &" ".repeat(rect.width as usize),
+            style,
+            rect.top_left(),
+            rect,
+        );
+        let mut bar = MenuBarUi {
+            ui: ui.child_ui(rect, Layout::TopLeftHorizontal),
+            titles: Vec::new(),
+            last_titles,
+            focus_first,
+        };
+        let inner = func(&mut bar);
+        ctx.insert_into_memory(self.id, bar.titles);
+        inner
+    }
+}
+
+/// Adds menus to a [`MenuBar`]
+pub struct MenuBarUi {
+    ui: Ui,
+    /// id and screen area of every title shown so far
+    titles: Vec<(Id, Rect)>,
+    last_titles: Vec<(Id, Rect)>,
+    focus_first: bool,
+}
+
+impl MenuBarUi {
+    /// A title that opens `func` in a menu below it. An `&` in the title marks
+    /// the letter that opens it with alt, otherwise the first letter does
+    pub fn menu(&mut self, title: &str, func: impl FnOnce(&mut Ui)) -> Response {
+        let ctx = self.ui.ctx().clone();
+        let default_style = *ctx.style().borrow();
+        let (label, mnemonic) = mnemonic(title);
+        let rect = self
+            .ui
+            .allocate_size(VecI2::new(str_width(&label, None) + 2, 1));
+        let id = self.ui.id().with(&label);
+        let response = self.ui.interact(id, rect);
+        let screen_rect = ctx.to_screen(rect);
+        if self.focus_first && self.titles.is_empty() {
+            response.request_focus(&ctx);
+        }
+        self.titles.push((id, screen_rect));
+
+        let mouse = ctx.input().mouse;
+        let pointed = response.hovered()
+            && mouse.delta() != (0, 0)
+            && mouse.position.is_some_and(|pos| rect.contains(pos));
+        let other_open = self
+            .last_titles
+            .iter()
+            .any(|(other, _)| *other != id && is_menu_open(&ctx, *other));
+        let alt = mnemonic.is_some_and(|(_, c)| {
+            ctx.consume_shortcut(&[Shortcut::new(KeyModifiers::ALT, KeyCode::Char(c))])
+        });
+        // down opens the menu instead of moving focus out of the bar
+        if response.focused() {
+            ctx.lock_focus(FocusLock {
+                horizontal: false,
+                vertical: true,
+                tab: false,
+            });
+        }
+        let down = response.focused()
+            && ctx.consume_shortcut(&[Shortcut::new(KeyModifiers::NONE, KeyCode::Down)]);
+
+        if is_menu_open(&ctx, id) && response.clicked() {
+            close_menu(&ctx);
+        } else if response.clicked() || alt || down || (other_open && pointed) {
+            let refocus = (response.focused() || alt).then_some(screen_rect);
+            open_menu(&ctx, id, screen_rect, refocus);
+        }
+
+        let open = show_menu(&ctx, id, func);
+        if open {
+            let left = ctx.consume_shortcut(&[Shortcut::new(KeyModifiers::NONE, KeyCode::Left)]);
+            let right = ctx.consume_shortcut(&[Shortcut::new(KeyModifiers::NONE, KeyCode::Right)]);
+            let count = self.last_titles.len();
+            let index = self.last_titles.iter().position(|(other, _)| *other == id);
+            if let Some(index) = index.filter(|_| left || right) {
+                let next = (if right { index + 1 } else { index + count - 1 }) % count;
+                let (next, rect) = self.last_titles[next];
+                open_menu(&ctx, next, rect, Some(rect));
+            }
+        }
+
+        let style = if open || response.focused() {
+            default_style.list_highlight
+        } else if response.hovered() {
+            default_style.button_hovered
+        } else {
+            default_style.button
+        };
+        self.ui
+            .draw(&format!(" {label} "), style, rect.top_left(), rect);
+        if let Some((offset, c)) = mnemonic {
+            let mut underlined = style;
+            underlined.attributes.set(Attribute::Underlined);
+            let pos = VecI2::new(rect.x + 1 + str_width(&label[..offset], None), rect.y);
+            self.ui.draw(&c.to_string(), underlined, pos, rect);
+        }
+        response
+    }
+}
+
+/// `title` without the `&` marking its mnemonic, and the byte offset and
+/// character of the mnemonic, which is the first letter when none is marked
+pub(crate) fn mnemonic(title: &str) -> (String, Option<(usize, char)>) {
+    match title.split_once('&') {
+        Some((before, after)) => {
+            let label = format!("{before}{after}");
+            (label, after.chars().next().map(|c| (before.len(), c)))
+        }
+        None => (
+            title.to_string(),
+            title.char_indices().find(|(_, c)| c.is_alphanumeric()),
+        ),
+    }
+}
+
+#[test]
+pub fn test_mnemonic() {
+    assert_eq!(mnemonic("File"), ("File".to_string(), Some((0, 'F'))));
+    assert_eq!(mnemonic("E&xit"), ("Exit".to_string(), Some((1, 'x'))));
+    assert_eq!(mnemonic("&Edit"), ("Edit".to_string(), Some((0, 'E'))));
+    assert_eq!(mnemonic("  ?"), ("  ?".to_string(), None));
+}
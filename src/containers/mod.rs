@@ -1,3 +1,14 @@
 pub mod bordered;
+pub mod drag_source;
 pub mod drop_down;
 pub mod frame;
+pub mod grid;
+pub mod list_view;
+pub mod menu;
+pub mod menu_bar;
+pub mod modal;
+pub mod popup;
+pub mod scroll_area;
+pub mod split_pane;
+pub mod tooltip;
+pub mod window;
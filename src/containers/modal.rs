@@ -0,0 +1,222 @@
+use std::num::NonZeroU8;
+
+use crossterm::event::KeyCode;
+
+use crate::{
+    context::Context,
+    id::Id,
+    layers,
+    math_util::{Rect, VecI2},
+    style::Style,
+    ui::{Layout, Ui},
+    widgets::text_edit::str_width,
+};
+
+use super::popup::{draw_border, fill_box};
+
+pub struct ModalResponse<R> {
+    /// what the contents returned, `Some` once the dialog was answered
+    pub result: Option<R>,
+    /// escape was pressed
+    pub cancelled: bool,
+    /// the area covered including the border
+    pub rect: Rect,
+}
+
+impl<R> ModalResponse<R> {
+    /// The dialog was answered or cancelled and should not be shown anymore
+    pub fn closed(&self) -> bool {
+        self.result.is_some() || self.cancelled
+    }
+}
+
+/// A dialog centered on the screen on its own layer. Everything below it is
+/// dimmed and does not get the mouse, and tab and the arrow keys only move
+/// focus between the widgets in the dialog. Keep showing it every frame until
+/// it is answered or cancelled.
+pub struct Modal {
+    id: Id,
+    title: Option<String>,
+    layer: NonZeroU8,
+    dim: bool,
+    close_on_escape: bool,
+    size: Option<VecI2>,
+    style: Option<Style>,
+}
+
+impl Modal {
+    pub fn new(id_source: impl std::hash::Hash) -> Self {
+        Self {
+            id: Id::new(id_source),
+            title: None,
+            layer: layers::MODAL,
+            dim: true,
+            close_on_escape: true,
+            size: None,
+            style: None,
+        }
+    }
+
+    /// Shown in the top border
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn layer(mut self, layer: NonZeroU8) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    /// Dims everything below the dialog, on by default
+    pub fn dim(mut self, dim: bool) -> Self {
+        self.dim = dim;
+        self
+    }
+
+    /// Escape cancels the dialog, on by default
+    pub fn close_on_escape(mut self, close: bool) -> Self {
+        self.close_on_escape = close;
+        self
+    }
+
+    /// Size of the contents, otherwise the size they took last frame is used
+    pub fn fixed_size(mut self, size: VecI2) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Shows `func` in the dialog, it returns `Some` once the dialog is answered
+    pub fn show<R>(
+        self,
+        ctx: &Context,
+        func: impl FnOnce(&mut Ui) -> Option<R>,
+    ) -> ModalResponse<R> {
+        ctx.unscrolled(|| self.show_inner(ctx, func))
+    }
+
+    /// A question with a button to agree and one to refuse, cancelling counts as refusing
+    pub fn confirm(self, ctx: &Context, message: &str, yes: &str, no: &str) -> ModalResponse<bool> {
+        let mut response = self.show(ctx, |ui| {
+            ui.label(message);
+            ui.add_space_primary_direction(1);
+            ui.horizontal(|ui| {
+                if ui.button(yes).clicked() {
+                    return Some(true);
+                }
+                ui.add_space_primary_direction(2);
+                ui.button(no).clicked().then_some(false)
+            })
+        });
+        if response.cancelled {
+            response.result = Some(false);
+        }
+        response
+    }
+
+    fn show_inner<R>(
+        self,
+        ctx: &Context,
+        func: impl FnOnce(&mut Ui) -> Option<R>,
+    ) -> ModalResponse<R> {
+        let screen = ctx.screen_rect();
+        let default_style = *ctx.style().borrow();
+        let style = self.style.unwrap_or(default_style.popup);
+        let title_width = self
+            .title
+            .as_ref()
+            .map_or(0, |title| str_width(title, None) + 2);
+
+        let content = self
+            .size
+            .unwrap_or_else(|| ctx.get_memory_or(self.id, VecI2::new(title_width, 1)));
+        let size = VecI2::new(
+            content
+                .x
+                .max(title_width)
+                .saturating_add(2)
+                .min(screen.width),
+            content.y.saturating_add(2).min(screen.height),
+        );
+        let rect = Rect::new_pos_size(
+            VecI2::new((screen.width - size.x) / 2, (screen.height - size.y) / 2),
+            size,
+        );
+
+        // nothing below gets the mouse while the dialog is up
+        ctx.register_layer_area(screen, self.layer);
+        if self.dim {
+            ctx.dim_below(self.layer);
+        }
+
+        fill_box(ctx, rect, self.layer, style);
+        draw_border(ctx, rect, self.layer, style);
+        if let Some(title) = &self.title {
+            ctx.draw(
+                &format!(" {title} "),
+                style,
+                VecI2::new(rect.x + 1, rect.y),
+                self.layer,
+                Rect::new_pos_size(
+                    VecI2::new(rect.x + 1, rect.y),
+                    VecI2::new(rect.width.saturating_sub(2), 1),
+                ),
+            );
+        }
+
+        // contents that do not fit yet may grow up to the screen edge, the
+        // dialog is sized to them next frame
+        let mut inner_rect = rect;
+        inner_rect.shrink_evenly(1);
+        if self.size.is_none() {
+            inner_rect = Rect::new_pos_size(
+                inner_rect.top_left(),
+                VecI2::new(
+                    screen.width.saturating_sub(inner_rect.x + 1),
+                    screen.height.saturating_sub(inner_rect.y + 1),
+                ),
+            );
+        }
+        let mut ui = Ui::new(
+            ctx.clone(),
+            Layout::TopLeftVertical,
+            self.id.with("modal"),
+            inner_rect,
+            self.layer,
+        );
+        let first = ctx.focus().borrow().count();
+        let result = func(&mut ui);
+        let last = ctx.focus().borrow().count();
+        ctx.trap_focus(first..last);
+        if self.size.is_none() {
+            let used = ui.get_current().size();
+            if used != content {
+                ctx.request_redraw();
+            }
+            ctx.insert_into_memory(self.id, used);
+        }
+
+        let escape = ctx
+            .input()
+            .keyboard
+            .events
+            .iter()
+            .enumerate()
+            .position(|(i, key)| key.code == KeyCode::Esc && !ctx.is_key_consumed(i));
+        let cancelled = self.close_on_escape && result.is_none() && escape.is_some();
+        if let Some(index) = escape.filter(|_| cancelled) {
+            ctx.consume_key(index);
+        }
+
+        ModalResponse {
+            result,
+            cancelled,
+            rect,
+        }
+    }
+}
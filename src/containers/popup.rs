@@ -0,0 +1,265 @@
+use std::num::NonZeroU8;
+
+use crate::{
+    context::Context,
+    id::Id,
+    input::mouse::MouseButtonState,
+    layers,
+    math_util::{Rect, VecI2},
+    style::Style,
+    ui::{Layout, Ui},
+};
+
+pub struct PopupResponse<R> {
+    pub inner: R,
+    /// the area covered including the border
+    pub rect: Rect,
+    /// the primary button went down somewhere outside the popup this frame
+    pub clicked_outside: bool,
+}
+
+/// A bordered area floating on its own layer next to an anchor rect, placed below
+/// the anchor when it fits and above it otherwise. Widgets on lower layers do not
+/// get the mouse where the popup is.
+pub struct Popup {
+    id: Id,
+    anchor: Rect,
+    layer: NonZeroU8,
+    min_width: u16,
+    size: Option<VecI2>,
+    style: Option<Style>,
+}
+
+impl Popup {
+    pub fn new(id_source: impl std::hash::Hash, anchor: Rect) -> Self {
+        Self {
+            id: Id::new(id_source),
+            anchor,
+            layer: layers::POPUP,
+            min_width: 0,
+            size: None,
+            style: None,
+        }
+    }
+
+    pub fn layer(mut self, layer: NonZeroU8) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    /// Minimum width of the contents, not counting the border
+    pub fn min_width(mut self, min_width: u16) -> Self {
+        self.min_width = min_width;
+        self
+    }
+
+    /// Size of the contents, otherwise the size they took last frame is used
+    pub fn fixed_size(mut self, size: VecI2) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    pub fn show<R>(mut self, ctx: &Context, func: impl FnOnce(&mut Ui) -> R) -> PopupResponse<R> {
+        // popups float above any scroll area they were opened from
+        self.anchor = ctx.to_screen(self.anchor);
+        ctx.unscrolled(|| self.show_inner(ctx, func))
+    }
+
+    fn show_inner<R>(self, ctx: &Context, func: impl FnOnce(&mut Ui) -> R) -> PopupResponse<R> {
+        let screen = ctx.screen_rect();
+        let default_style = *ctx.style().borrow();
+        let style = self.style.unwrap_or(default_style.popup);
+
+        let content = self
+            .size
+            .unwrap_or_else(|| ctx.get_memory_or(self.id, VecI2::new(self.min_width, 1)));
+        let size = VecI2::new(
+            content
+                .x
+                .max(self.min_width)
+                .saturating_add(2)
+                .min(screen.width),
+            content.y.saturating_add(2).min(screen.height),
+        );
+
+        let rect = place_near(self.anchor, size, screen, Side::Below);
+        ctx.register_layer_area(rect, self.layer);
+        fill_box(ctx, rect, self.layer, style);
+        draw_border(ctx, rect, self.layer, style);
+
+        let mut inner_rect = rect;
+        inner_rect.shrink_evenly(1);
+        let mut ui = Ui::new(
+            ctx.clone(),
+            Layout::TopLeftVertical,
+            self.id.with("popup"),
+            inner_rect,
+            self.layer,
+        );
+        let inner = func(&mut ui);
+        if self.size.is_none() {
+            ctx.insert_into_memory(self.id, ui.get_current().size());
+        }
+
+        let clicked_outside = matches!(
+            ctx.input().mouse.buttons[0],
+            MouseButtonState::Down(pos) if !rect.contains(pos)
+        );
+
+        PopupResponse {
+            inner,
+            rect,
+            clicked_outside,
+        }
+    }
+}
+
+/// Where something floating next to an anchor goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Side {
+    /// below the anchor, or above it when there is no room below
+    Below,
+    /// right of the anchor, or left of it, with the first row inside the
+    /// border next to the anchor
+    Beside,
+    /// below or above the anchor, beside it when neither fits
+    Around,
+}
+
+/// Where a box of `size` goes next to `anchor` so it stays on the screen. Boxes
+/// sized to what their contents took last frame are redrawn when that changes
+pub(crate) fn place_near(anchor: Rect, size: VecI2, screen: Rect, side: Side) -> Rect {
+    let size = VecI2::new(size.x.min(screen.width), size.y.min(screen.height));
+    let max = VecI2::new(screen.width - size.x, screen.height - size.y);
+    let below = anchor.y.saturating_add(anchor.height);
+    let right = anchor.x.saturating_add(anchor.width);
+    let vertical = if below <= max.y {
+        Some(below)
+    } else {
+        anchor.y.checked_sub(size.y)
+    };
+    let horizontal = if right <= max.x {
+        Some(right)
+    } else {
+        anchor.x.checked_sub(size.x)
+    };
+
+    let pos = match (side, vertical, horizontal) {
+        (Side::Beside, _, x) => {
+            VecI2::new(x.unwrap_or(max.x), anchor.y.saturating_sub(1).min(max.y))
+        }
+        (Side::Around, None, Some(x)) => VecI2::new(x, anchor.y.min(max.y)),
+        (_, y, _) => VecI2::new(anchor.x.min(max.x), y.unwrap_or(max.y)),
+    };
+    Rect::new_pos_size(pos, size)
+}
+
+/// Blanks out whatever was drawn below `rect` on lower layers
+pub(crate) fn fill_box(ctx: &Context, rect: Rect, layer: NonZeroU8, style: Style) {
+    let blank = " ".repeat(rect.width as usize);
+    for row in rect.y..rect.y + rect.height {
+        ctx.draw(&blank, style, VecI2::new(rect.x, row), layer, rect);
+    }
+}
+
+/// Draws a box around the edge of `rect` with the current line set
+pub(crate) fn draw_border(ctx: &Context, rect: Rect, layer: NonZeroU8, style: Style) {
+    if rect.width < 2 || rect.height < 2 {
+        return;
+    }
+    let lines = ctx.style().borrow().lines;
+    let horizontal = lines.horizontal.repeat(rect.width as usize - 2);
+    let right = rect.x + rect.width - 1;
+    let bottom = rect.y + rect.height - 1;
+
+    ctx.draw(lines.top_left, style, rect.top_left(), layer, rect);
+    ctx.draw(
+        &horizontal,
+        style,
+        VecI2::new(rect.x + 1, rect.y),
+        layer,
+        rect,
+    );
+    ctx.draw(
+        lines.top_right,
+        style,
+        VecI2::new(right, rect.y),
+        layer,
+        rect,
+    );
+    for y in rect.y + 1..bottom {
+        ctx.draw(lines.vertical, style, VecI2::new(rect.x, y), layer, rect);
+        ctx.draw(lines.vertical, style, VecI2::new(right, y), layer, rect);
+    }
+    ctx.draw(
+        lines.bottom_left,
+        style,
+        VecI2::new(rect.x, bottom),
+        layer,
+        rect,
+    );
+    ctx.draw(
+        &horizontal,
+        style,
+        VecI2::new(rect.x + 1, bottom),
+        layer,
+        rect,
+    );
+    ctx.draw(
+        lines.bottom_right,
+        style,
+        VecI2::new(right, bottom),
+        layer,
+        rect,
+    );
+}
+
+#[test]
+pub fn test_place_near() {
+    let screen = Rect::new_pos_size(VecI2::new(0, 0), VecI2::new(80, 24));
+    let at = |x, y, w, h| Rect::new_pos_size(VecI2::new(x, y), VecI2::new(w, h));
+
+    let size = VecI2::new(12, 6);
+    // at the click, moved to stay on screen
+    assert_eq!(
+        place_near(at(10, 5, 0, 0), size, screen, Side::Below),
+        at(10, 5, 12, 6)
+    );
+    assert_eq!(
+        place_near(at(75, 5, 0, 0), size, screen, Side::Below),
+        at(68, 5, 12, 6)
+    );
+    assert_eq!(
+        place_near(at(10, 20, 0, 0), size, screen, Side::Below),
+        at(10, 14, 12, 6)
+    );
+    // submenus go right of the row with their first entry next to it, or left
+    assert_eq!(
+        place_near(at(10, 5, 14, 1), size, screen, Side::Beside),
+        at(24, 4, 12, 6)
+    );
+    assert_eq!(
+        place_near(at(60, 5, 14, 1), size, screen, Side::Beside),
+        at(48, 4, 12, 6)
+    );
+    assert_eq!(
+        place_near(at(60, 22, 14, 1), size, screen, Side::Beside),
+        at(48, 18, 12, 6)
+    );
+
+    let size = VecI2::new(10, 3);
+    let around = |anchor| place_near(anchor, size, screen, Side::Around);
+    // below, pushed left to stay on screen
+    assert_eq!(around(at(5, 2, 4, 1)), at(5, 3, 10, 3));
+    assert_eq!(around(at(75, 2, 4, 1)), at(70, 3, 10, 3));
+    // above when there is no room below
+    assert_eq!(around(at(5, 22, 4, 1)), at(5, 19, 10, 3));
+    // to the side of something as tall as the screen
+    assert_eq!(around(at(0, 0, 20, 24)), at(20, 0, 10, 3));
+    assert_eq!(around(at(60, 0, 20, 24)), at(50, 0, 10, 3));
+}
@@ -0,0 +1,390 @@
+use crossterm::event::KeyCode;
+
+use crate::{
+    id::Id,
+    input::mouse::MouseButtonState,
+    math_util::{Rect, VecI2},
+    ui::{Layout, Ui},
+};
+
+/// Wheel notches are multiplied by this
+const WHEEL_STEP: i16 = 3;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ScrollAreaState {
+    /// how far the content is scrolled right and down
+    pub offset: VecI2,
+    /// size of the content last frame
+    pub content_size: VecI2,
+    /// the cell of the thumb grabbed while dragging a scrollbar
+    pub grab: u16,
+}
+
+pub struct ScrollAreaResponse<R> {
+    pub inner: R,
+    /// the visible part of the content, without the scrollbars
+    pub viewport: Rect,
+    pub content_size: VecI2,
+    pub offset: VecI2,
+}
+
+/// Start and length of the scrollbar thumb on a track of `track` cells
+pub(crate) fn thumb(track: u16, view: usize, content: usize, offset: usize) -> (u16, u16) {
+    if content <= view || track == 0 {
+        return (0, track);
+    }
+    let (track, view, content, offset) = (track as u64, view as u64, content as u64, offset as u64);
+    let len = (track * view / content).clamp(1, track);
+    let max_offset = content - view;
+    let free = track - len;
+    let start = (offset.min(max_offset) * free + max_offset / 2) / max_offset;
+    (start as u16, len as u16)
+}
+
+/// Inverse of [`thumb`], the offset that puts the thumb at `start`
+pub(crate) fn offset_at(track: u16, view: usize, content: usize, start: u16) -> usize {
+    let (_, len) = thumb(track, view, content, 0);
+    let free = track.saturating_sub(len) as u64;
+    if free == 0 {
+        return 0;
+    }
+    let max_offset = content.saturating_sub(view) as u64;
+    ((start as u64).min(free) * max_offset / free) as usize
+}
+
+/// Moves `offset` when the scrollbar on `track` is clicked or dragged, `grab`
+/// remembers where the thumb was picked up
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn drag_scrollbar(
+    ui: &mut Ui,
+    id: Id,
+    track: Rect,
+    vertical: bool,
+    view: usize,
+    content: usize,
+    offset: &mut usize,
+    grab: &mut u16,
+) {
+    let bar = ui.interact(id, track);
+    let (length, origin) = if vertical {
+        (track.height, track.y)
+    } else {
+        (track.width, track.x)
+    };
+    let along = |pos: VecI2| if vertical { pos.y } else { pos.x }.saturating_sub(origin);
+    let (cell, down) = match bar.buttons[0] {
+        MouseButtonState::Down(pos) => (along(pos), true),
+        MouseButtonState::Drag { current, .. } => (along(current), false),
+        _ => return,
+    };
+    if down {
+        // grab the thumb where it was clicked or center it on the click
+        let (start, len) = thumb(length, view, content, *offset);
+        *grab = if (start..start + len).contains(&cell) {
+            cell - start
+        } else {
+            len / 2
+        };
+    }
+    *offset = offset_at(length, view, content, cell.saturating_sub(*grab));
+}
+
+pub(crate) fn draw_scrollbar(
+    ui: &mut Ui,
+    track: Rect,
+    vertical: bool,
+    view: usize,
+    content: usize,
+    offset: usize,
+) {
+    let default_style = *ui.ctx().style().borrow();
+    let symbols = default_style.scrollbars;
+    let length = if vertical { track.height } else { track.width };
+    let (start, len) = thumb(length, view, content, offset);
+    for i in 0..length {
+        let thumb = (start..start + len).contains(&i);
+        let (glyph, pos) = match (vertical, thumb) {
+            (true, true) => (symbols.vertical_thumb, VecI2::new(track.x, track.y + i)),
+            (true, false) => (symbols.vertical_track, VecI2::new(track.x, track.y + i)),
+            (false, true) => (symbols.horizontal_thumb, VecI2::new(track.x + i, track.y)),
+            (false, false) => (symbols.horizontal_track, VecI2::new(track.x + i, track.y)),
+        };
+        ui.draw(
+            glyph,
+            default_style.button,
+            pos,
+            Rect::new_pos_size(pos, VecI2::new(1, 1)),
+        );
+    }
+}
+
+/// Shows content larger than the space it gets, scrolled with the wheel, by
+/// dragging the scrollbars, page up/down and by moving focus to a widget out of view
+pub struct ScrollArea {
+    id_source: Id,
+    horizontal: bool,
+    vertical: bool,
+    max_size: VecI2,
+    focusable: bool,
+}
+
+impl ScrollArea {
+    fn new(id_source: impl std::hash::Hash, horizontal: bool, vertical: bool) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            horizontal,
+            vertical,
+            max_size: VecI2::new(u16::MAX, u16::MAX),
+            focusable: false,
+        }
+    }
+
+    pub fn vertical(id_source: impl std::hash::Hash) -> Self {
+        Self::new(id_source, false, true)
+    }
+
+    pub fn horizontal(id_source: impl std::hash::Hash) -> Self {
+        Self::new(id_source, true, false)
+    }
+
+    pub fn both(id_source: impl std::hash::Hash) -> Self {
+        Self::new(id_source, true, true)
+    }
+
+    /// Limits the width including the scrollbar, defaults to all the space available
+    pub fn max_width(mut self, width: u16) -> Self {
+        self.max_size.x = width;
+        self
+    }
+
+    /// Limits the height including the scrollbar, defaults to all the space available
+    pub fn max_height(mut self, height: u16) -> Self {
+        self.max_size.y = height;
+        self
+    }
+
+    /// Makes the viewport a focus stop so page up/down scroll it from the
+    /// keyboard, for content without focusable widgets of its own
+    pub fn focusable(mut self, focusable: bool) -> Self {
+        self.focusable = focusable;
+        self
+    }
+
+    pub fn show<R>(self, ui: &mut Ui, func: impl FnOnce(&mut Ui) -> R) -> ScrollAreaResponse<R> {
+        let ctx = ui.ctx().clone();
+        let id = ui.id().with(self.id_source);
+        let mut state = ctx.get_memory_or(id, ScrollAreaState::default());
+
+        let available = ui.get_max();
+        let max = VecI2::new(
+            available.width.min(self.max_size.x),
+            available.height.min(self.max_size.y),
+        );
+
+        // a scrollbar only takes space when the content does not fit
+        let content = state.content_size;
+        let mut show_vertical = self.vertical && content.y > max.y;
+        let show_horizontal =
+            self.horizontal && content.x > max.x.saturating_sub(show_vertical as u16);
+        show_vertical |= self.vertical && content.y > max.y.saturating_sub(show_horizontal as u16);
+        let bars = VecI2::new(show_vertical as u16, show_horizontal as u16);
+
+        let size = VecI2::new(
+            content.x.min(max.x.saturating_sub(bars.x)),
+            content.y.min(max.y.saturating_sub(bars.y)),
+        );
+        let outer = ui.allocate_size(size + bars);
+        let viewport = Rect::new_pos_size(outer.top_left(), size);
+
+        // the content gets all the room it wants along the scrolled axes
+        let room = |scrolled: bool, start: u16, limit: u16| {
+            if scrolled {
+                (u16::MAX / 2).saturating_sub(start)
+            } else {
+                limit
+            }
+        };
+        let content_rect = Rect::new_pos_size(
+            viewport.top_left(),
+            VecI2::new(
+                room(self.horizontal, viewport.x, max.x.saturating_sub(bars.x)),
+                room(self.vertical, viewport.y, max.y.saturating_sub(bars.y)),
+            ),
+        );
+        let mut content_ui = Ui::new(
+            ctx.clone(),
+            // the content grows away from the top left corner the offset is measured from
+            Layout::TopLeftVertical,
+            id.with("content"),
+            content_rect,
+            ui.layer(),
+        );
+
+        let drawn_offset = state.offset;
+        let first_focusable = ctx.focus().borrow().count();
+        let (inner, focused_area) = ctx.scrolled(viewport, state.offset, || {
+            let inner = func(&mut content_ui);
+            (inner, ctx.focused_area())
+        });
+        let (focus_inside, focus_changed) = {
+            let focus = ctx.focus().borrow();
+            let changed = focus.focused() != focus.last_focused();
+            let inside = focus
+                .focused_index()
+                .is_some_and(|index| (first_focusable..focus.count()).contains(&index));
+            (inside, changed)
+        };
+
+        let new_content = content_ui.get_current().size();
+        if new_content != state.content_size {
+            state.content_size = new_content;
+            ctx.request_redraw();
+        }
+
+        // interact after the content so widgets inside get the mouse first
+        let response = if self.focusable {
+            ui.interact_scroll(id, viewport)
+        } else {
+            ui.interact_scroll_mouse(id, viewport)
+        };
+        if response.scrolled() {
+            let (dx, dy) = response.scroll_delta();
+            if self.horizontal {
+                state.offset.x = state.offset.x.saturating_add_signed(dx * WHEEL_STEP);
+            }
+            if self.vertical {
+                // without a horizontal scrollbar shift+wheel scrolls vertically
+                let dy = if dy == 0 && !self.horizontal { -dx } else { dy };
+                state.offset.y = state.offset.y.saturating_add_signed(-dy * WHEEL_STEP);
+            }
+        }
+
+        if self.vertical && (response.hovered() || focus_inside) {
+            for (index, key) in ctx.input().keyboard.events.iter().enumerate() {
+                if ctx.is_key_consumed(index) {
+                    continue;
+                }
+                let page = size.y.saturating_sub(1).max(1);
+                match key.code {
+                    KeyCode::PageUp => state.offset.y = state.offset.y.saturating_sub(page),
+                    KeyCode::PageDown => state.offset.y = state.offset.y.saturating_add(page),
+                    _ => continue,
+                }
+                ctx.consume_key(index);
+            }
+        }
+
+        // bring a widget focus just moved to into view
+        if let Some(area) = focused_area.filter(|_| focus_inside && focus_changed) {
+            let start = VecI2::new(
+                area.x.saturating_sub(viewport.x),
+                area.y.saturating_sub(viewport.y),
+            );
+            let end = start + area.size();
+            if self.horizontal {
+                if start.x < state.offset.x {
+                    state.offset.x = start.x;
+                } else if end.x > state.offset.x + size.x {
+                    state.offset.x = end.x.saturating_sub(size.x).min(start.x);
+                }
+            }
+            if self.vertical {
+                if start.y < state.offset.y {
+                    state.offset.y = start.y;
+                } else if end.y > state.offset.y + size.y {
+                    state.offset.y = end.y.saturating_sub(size.y).min(start.y);
+                }
+            }
+        }
+
+        if show_vertical {
+            let track = Rect::new_pos_size(
+                VecI2::new(viewport.x + size.x, viewport.y),
+                VecI2::new(1, size.y),
+            );
+            let mut offset = state.offset.y as usize;
+            drag_scrollbar(
+                ui,
+                id.with("vertical"),
+                track,
+                true,
+                size.y as usize,
+                new_content.y as usize,
+                &mut offset,
+                &mut state.grab,
+            );
+            state.offset.y = offset as u16;
+        }
+        if show_horizontal {
+            let track = Rect::new_pos_size(
+                VecI2::new(viewport.x, viewport.y + size.y),
+                VecI2::new(size.x, 1),
+            );
+            let mut offset = state.offset.x as usize;
+            drag_scrollbar(
+                ui,
+                id.with("horizontal"),
+                track,
+                false,
+                size.x as usize,
+                new_content.x as usize,
+                &mut offset,
+                &mut state.grab,
+            );
+            state.offset.x = offset as u16;
+        }
+
+        state.offset.x = state.offset.x.min(new_content.x.saturating_sub(size.x));
+        state.offset.y = state.offset.y.min(new_content.y.saturating_sub(size.y));
+
+        if show_vertical {
+            let track = Rect::new_pos_size(
+                VecI2::new(viewport.x + size.x, viewport.y),
+                VecI2::new(1, size.y),
+            );
+            let (view, content) = (size.y as usize, new_content.y as usize);
+            draw_scrollbar(ui, track, true, view, content, state.offset.y as usize);
+        }
+        if show_horizontal {
+            let track = Rect::new_pos_size(
+                VecI2::new(viewport.x, viewport.y + size.y),
+                VecI2::new(size.x, 1),
+            );
+            let (view, content) = (size.x as usize, new_content.x as usize);
+            draw_scrollbar(ui, track, false, view, content, state.offset.x as usize);
+        }
+
+        // the content was already drawn with the old offset
+        if state.offset != drawn_offset {
+            ctx.request_redraw();
+        }
+        ctx.insert_into_memory(id, state);
+        ScrollAreaResponse {
+            inner,
+            viewport,
+            content_size: new_content,
+            offset: state.offset,
+        }
+    }
+}
+
+#[test]
+pub fn test_scrollbar_thumb() {
+    // everything fits
+    assert_eq!(thumb(10, 10, 5, 0), (0, 10));
+    // half the content is visible
+    assert_eq!(thumb(10, 10, 20, 0), (0, 5));
+    assert_eq!(thumb(10, 10, 20, 10), (5, 5));
+    assert_eq!(thumb(10, 10, 20, 5), (3, 5));
+    // a tiny view still gets a thumb
+    assert_eq!(thumb(10, 10, 1000, 990).1, 1);
+    assert_eq!(thumb(10, 10, 1000, 990).0, 9);
+
+    for offset in [0, 3, 7, 10] {
+        let (start, _) = thumb(10, 10, 20, offset);
+        let back = offset_at(10, 10, 20, start);
+        assert_eq!(thumb(10, 10, 20, back).0, start);
+    }
+    assert_eq!(offset_at(10, 10, 20, 5), 10);
+    assert_eq!(offset_at(10, 10, 20, 99), 10);
+}
@@ -0,0 +1,296 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::{
+    context::FocusLock,
+    id::Id,
+    input::mouse::MouseButtonState,
+    math_util::{Rect, VecI2},
+    response::Response,
+    ui::{Layout, Ui},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitDirection {
+    /// side by side with a vertical divider
+    Horizontal,
+    /// one above the other with a horizontal divider
+    Vertical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    First,
+    Second,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SplitState {
+    /// share of the space the first side gets
+    pub ratio: f32,
+    /// the side that is hidden, the other one gets all the space
+    pub collapsed: Option<Side>,
+}
+
+pub struct SplitResponse<A, B> {
+    pub first: A,
+    pub second: B,
+    /// the divider, focus it to resize with the keyboard
+    pub divider: Response,
+}
+
+/// Divides the space left in a ui between two children with a divider that can
+/// be dragged. When focused the arrow keys move the divider, and home and end
+/// collapse the first or second side if the split is collapsible.
+pub struct SplitPane {
+    id_source: Id,
+    direction: SplitDirection,
+    default_ratio: f32,
+    min: [u16; 2],
+    max: [u16; 2],
+    collapsible: bool,
+}
+
+impl SplitPane {
+    pub fn new(id_source: impl std::hash::Hash, direction: SplitDirection) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            direction,
+            default_ratio: 0.5,
+            min: [0, 0],
+            max: [u16::MAX, u16::MAX],
+            collapsible: false,
+        }
+    }
+
+    /// Children side by side
+    pub fn horizontal(id_source: impl std::hash::Hash) -> Self {
+        Self::new(id_source, SplitDirection::Horizontal)
+    }
+
+    /// Children one above the other
+    pub fn vertical(id_source: impl std::hash::Hash) -> Self {
+        Self::new(id_source, SplitDirection::Vertical)
+    }
+
+    /// Share of the space the first side gets until the divider is moved
+    pub fn default_ratio(mut self, ratio: f32) -> Self {
+        self.default_ratio = ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn min_size(mut self, side: Side, size: u16) -> Self {
+        self.min[side as usize] = size;
+        self
+    }
+
+    pub fn max_size(mut self, side: Side, size: u16) -> Self {
+        self.max[side as usize] = size;
+        self
+    }
+
+    /// Dragging the divider close to an edge or pressing home or end hides a side
+    pub fn collapsible(mut self, collapsible: bool) -> Self {
+        self.collapsible = collapsible;
+        self
+    }
+
+    pub fn show<A, B>(
+        self,
+        ui: &mut Ui,
+        first: impl FnOnce(&mut Ui) -> A,
+        second: impl FnOnce(&mut Ui) -> B,
+    ) -> SplitResponse<A, B> {
+        let ctx = ui.ctx().clone();
+        let id = ui.id().with(self.id_source);
+        let default_style = *ctx.style().borrow();
+        let mut state = ctx.get_memory_or(
+            id,
+            SplitState {
+                ratio: self.default_ratio,
+                collapsed: None,
+            },
+        );
+
+        let rect = ui.get_max();
+        let _ = ui.allocate_area(rect);
+        let horizontal = self.direction == SplitDirection::Horizontal;
+        let (start, length) = if horizontal {
+            (rect.x, rect.width)
+        } else {
+            (rect.y, rect.height)
+        };
+        let space = length.saturating_sub(1);
+        let size = first_size(space, &state, self.min, self.max);
+
+        let divider_rect = if horizontal {
+            Rect::new_pos_size(VecI2::new(start + size, rect.y), VecI2::new(1, rect.height))
+        } else {
+            Rect::new_pos_size(VecI2::new(rect.x, start + size), VecI2::new(rect.width, 1))
+        };
+        let divider = ui.interact(id.with("divider"), divider_rect);
+        if divider.pressed() {
+            divider.request_focus(&ctx);
+        }
+
+        let origin = rect.top_left();
+        let dragged_to = match divider.buttons[0] {
+            MouseButtonState::Drag { current, .. } if horizontal => {
+                Some(current.x as i32 - origin.x as i32)
+            }
+            MouseButtonState::Drag { current, .. } => Some(current.y as i32 - origin.y as i32),
+            _ => None,
+        };
+
+        // arrows along the split move the divider instead of focus
+        let mut moved_to = dragged_to;
+        if divider.focused() {
+            ctx.lock_focus(FocusLock {
+                horizontal,
+                vertical: !horizontal,
+                tab: false,
+            });
+            let (back, forward) = if horizontal {
+                (KeyCode::Left, KeyCode::Right)
+            } else {
+                (KeyCode::Up, KeyCode::Down)
+            };
+            let keys = ctx.input().keyboard.events.clone();
+            for (i, key) in keys.iter().enumerate() {
+                if ctx.is_key_consumed(i) {
+                    continue;
+                }
+                let step = if key.modifiers.contains(KeyModifiers::SHIFT) {
+                    5
+                } else {
+                    1
+                };
+                let current = moved_to.unwrap_or(size as i32);
+                match key.code {
+                    // a collapsed side comes back at the size it had before
+                    code if (code == back || code == forward) && state.collapsed.is_some() => {
+                        state.collapsed = None
+                    }
+                    code if code == back => moved_to = Some(current - step),
+                    code if code == forward => moved_to = Some(current + step),
+                    KeyCode::Home if self.collapsible => state.collapsed = Some(Side::First),
+                    KeyCode::End if self.collapsible => state.collapsed = Some(Side::Second),
+                    _ => continue,
+                }
+                ctx.consume_key(i);
+            }
+        }
+        if let Some(desired) = moved_to {
+            let rest = space as i32 - desired;
+            state.collapsed = if self.collapsible && desired <= self.min[0] as i32 / 2 {
+                Some(Side::First)
+            } else if self.collapsible && rest <= self.min[1] as i32 / 2 {
+                Some(Side::Second)
+            } else {
+                None
+            };
+            if state.collapsed.is_none() && space > 0 {
+                let clamped = desired.clamp(0, space as i32) as u16;
+                let bounded = first_size(
+                    space,
+                    &SplitState {
+                        ratio: clamped as f32 / space as f32,
+                        collapsed: None,
+                    },
+                    self.min,
+                    self.max,
+                );
+                state.ratio = bounded as f32 / space as f32;
+            }
+        }
+
+        let style = if divider.dragged() || divider.focused() {
+            default_style.list_highlight
+        } else if divider.hovered() {
+            default_style.button_hovered
+        } else {
+            default_style.button
+        };
+        let lines = default_style.lines;
+        if horizontal {
+            for y in divider_rect.y..divider_rect.y + divider_rect.height {
+                ui.draw(
+                    lines.vertical,
+                    style,
+                    VecI2::new(divider_rect.x, y),
+                    divider_rect,
+                );
+            }
+        } else {
+            let line = lines.horizontal.repeat(divider_rect.width as usize);
+            ui.draw(&line, style, divider_rect.top_left(), divider_rect);
+        }
+
+        let (first_rect, second_rect) = if horizontal {
+            (
+                Rect::new_pos_size(rect.top_left(), VecI2::new(size, rect.height)),
+                Rect::new_pos_size(
+                    VecI2::new(start + size + 1, rect.y),
+                    VecI2::new(space - size, rect.height),
+                ),
+            )
+        } else {
+            (
+                Rect::new_pos_size(rect.top_left(), VecI2::new(rect.width, size)),
+                Rect::new_pos_size(
+                    VecI2::new(rect.x, start + size + 1),
+                    VecI2::new(rect.width, space - size),
+                ),
+            )
+        };
+        let mut first_ui = ui.child_ui(first_rect, Layout::TopLeftVertical);
+        let first = ctx.scrolled(first_rect, VecI2::new(0, 0), || first(&mut first_ui));
+        let mut second_ui = ui.child_ui(second_rect, Layout::TopLeftVertical);
+        let second = ctx.scrolled(second_rect, VecI2::new(0, 0), || second(&mut second_ui));
+
+        if first_size(space, &state, self.min, self.max) != size {
+            ctx.request_redraw();
+        }
+        ctx.insert_into_memory(id, state);
+        SplitResponse {
+            first,
+            second,
+            divider,
+        }
+    }
+}
+
+/// How much of `space` the first side gets, keeping both sides within their
+/// min and max sizes where possible
+pub(crate) fn first_size(space: u16, state: &SplitState, min: [u16; 2], max: [u16; 2]) -> u16 {
+    match state.collapsed {
+        Some(Side::First) => return 0,
+        Some(Side::Second) => return space,
+        None => {}
+    }
+    let wanted = (state.ratio.clamp(0.0, 1.0) * space as f32).round() as u16;
+    let low = min[0].max(space.saturating_sub(max[1]));
+    let high = max[0].min(space.saturating_sub(min[1]));
+    // when both minimums do not fit the first side wins
+    wanted.min(high).max(low).min(space)
+}
+
+#[test]
+pub fn test_split_size() {
+    let state = |ratio| SplitState {
+        ratio,
+        collapsed: None,
+    };
+    let free = [u16::MAX, u16::MAX];
+    assert_eq!(first_size(99, &state(0.5), [0, 0], free), 50);
+    assert_eq!(first_size(100, &state(0.1), [20, 0], free), 20);
+    assert_eq!(first_size(100, &state(0.9), [0, 30], free), 70);
+    assert_eq!(first_size(100, &state(0.9), [0, 0], [40, u16::MAX]), 40);
+    assert_eq!(first_size(100, &state(0.1), [0, 0], [u16::MAX, 60]), 40);
+    assert_eq!(first_size(30, &state(0.5), [20, 20], free), 20);
+
+    let collapsed = SplitState {
+        ratio: 0.5,
+        collapsed: Some(Side::Second),
+    };
+    assert_eq!(first_size(100, &collapsed, [20, 20], free), 100);
+}
@@ -0,0 +1,79 @@
+use std::{num::NonZeroU8, time::Duration};
+
+use crate::{
+    context::Context,
+    id::Id,
+    layers,
+    math_util::{Rect, VecI2},
+    style::Style,
+    ui::{Layout, Ui},
+};
+
+use super::{
+    bordered::Bordered,
+    popup::{fill_box, place_near, Side},
+};
+
+/// How long a widget has to be hovered or focused before its tooltip shows
+pub const TOOLTIP_DELAY: Duration = Duration::from_millis(500);
+
+/// A bordered area next to an anchor rect that does not take the mouse. It goes
+/// below the anchor when it fits, otherwise above, to the right or to the left.
+/// Usually shown through [`crate::response::Response::on_hover_text`].
+pub struct Tooltip {
+    id: Id,
+    anchor: Rect,
+    layer: NonZeroU8,
+    style: Option<Style>,
+}
+
+impl Tooltip {
+    pub fn new(id_source: impl std::hash::Hash, anchor: Rect) -> Self {
+        Self {
+            id: Id::new(id_source),
+            anchor,
+            layer: layers::TOOLTIP,
+            style: None,
+        }
+    }
+
+    pub fn layer(mut self, layer: NonZeroU8) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Returns the area covered including the border
+    pub fn show(mut self, ctx: &Context, func: impl FnOnce(&mut Ui)) -> Rect {
+        self.anchor = ctx.to_screen(self.anchor);
+        ctx.unscrolled(|| self.show_inner(ctx, func))
+    }
+
+    fn show_inner(self, ctx: &Context, func: impl FnOnce(&mut Ui)) -> Rect {
+        let screen = ctx.screen_rect();
+        let style = self.style.unwrap_or(ctx.style().borrow().tooltip);
+
+        let size = ctx.get_memory_or(self.id, VecI2::new(3, 3));
+        let rect = place_near(self.anchor, size, screen, Side::Around);
+        fill_box(ctx, rect, self.layer, style);
+
+        let mut ui = Ui::new(
+            ctx.clone(),
+            Layout::TopLeftVertical,
+            self.id.with("tooltip"),
+            Rect::new_pos_pos(rect.top_left(), screen.bottom_right()),
+            self.layer,
+        );
+        Bordered::new().set_borders_style(style).show(&mut ui, func);
+        let used = ui.get_current().size();
+        if used != size {
+            ctx.request_redraw();
+        }
+        ctx.insert_into_memory(self.id, used);
+        rect
+    }
+}
@@ -0,0 +1,455 @@
+use std::num::NonZeroU8;
+
+use crate::{
+    context::Context,
+    id::Id,
+    input::mouse::MouseButtonState,
+    layers,
+    math_util::{Rect, VecI2},
+    response::Response,
+    style::Style,
+    ui::{Layout, Ui},
+    widgets::text_edit::str_width,
+};
+
+use super::popup::{draw_border, fill_box};
+
+pub struct WindowResponse<R> {
+    /// what the contents returned, `None` while the window is collapsed
+    pub inner: Option<R>,
+    /// the area covered including the border
+    pub rect: Rect,
+    pub collapsed: bool,
+}
+
+#[derive(Clone, Copy)]
+struct WindowState {
+    pos: VecI2,
+    size: VecI2,
+    collapsed: bool,
+    /// where the window was when the title bar or a border was grabbed
+    origin: Rect,
+}
+
+/// Which borders a resize grip moves
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) struct Grip {
+    left: bool,
+    right: bool,
+    bottom: bool,
+}
+
+impl Grip {
+    const LEFT: Self = Self::new(true, false, false);
+    const RIGHT: Self = Self::new(false, true, false);
+    const BOTTOM: Self = Self::new(false, false, true);
+    const BOTTOM_LEFT: Self = Self::new(true, false, true);
+    const BOTTOM_RIGHT: Self = Self::new(false, true, true);
+
+    const fn new(left: bool, right: bool, bottom: bool) -> Self {
+        Self {
+            left,
+            right,
+            bottom,
+        }
+    }
+}
+
+/// A bordered window floating above the frame that can be moved by dragging
+/// its title bar and resized by dragging its borders. Clicking a window brings
+/// it in front of the others. Position, size and whether it is collapsed are
+/// remembered by id, which defaults to the title.
+pub struct Window<'a> {
+    id: Id,
+    title: String,
+    open: Option<&'a mut bool>,
+    default_pos: Option<VecI2>,
+    default_size: VecI2,
+    min_size: VecI2,
+    collapsible: bool,
+    resizable: bool,
+    style: Option<Style>,
+}
+
+impl<'a> Window<'a> {
+    pub fn new(title: impl Into<String>) -> Self {
+        let title = title.into();
+        Self {
+            id: Id::new(&title),
+            title,
+            open: None,
+            default_pos: None,
+            default_size: VecI2::new(30, 10),
+            min_size: VecI2::new(4, 3),
+            collapsible: true,
+            resizable: true,
+            style: None,
+        }
+    }
+
+    /// Use when several windows share a title
+    pub fn id_source(mut self, id_source: impl std::hash::Hash) -> Self {
+        self.id = Id::new(id_source);
+        self
+    }
+
+    /// Shows a close button that sets `open` to false, nothing is shown while it is false
+    pub fn open(mut self, open: &'a mut bool) -> Self {
+        self.open = Some(open);
+        self
+    }
+
+    /// Where the top left corner goes the first time the window is shown,
+    /// centered on the screen otherwise
+    pub fn default_pos(mut self, pos: VecI2) -> Self {
+        self.default_pos = Some(pos);
+        self
+    }
+
+    /// Size including the border the first time the window is shown
+    pub fn default_size(mut self, size: VecI2) -> Self {
+        self.default_size = size;
+        self
+    }
+
+    /// Smallest size including the border the window can be resized to
+    pub fn min_size(mut self, size: VecI2) -> Self {
+        self.min_size = size;
+        self
+    }
+
+    /// Shows a button in the title bar that hides the contents, on by default
+    pub fn collapsible(mut self, collapsible: bool) -> Self {
+        self.collapsible = collapsible;
+        self
+    }
+
+    /// Dragging the left, right and bottom borders resizes the window, on by default
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Returns `None` when the window is closed
+    pub fn show<R>(
+        self,
+        ctx: &Context,
+        func: impl FnOnce(&mut Ui) -> R,
+    ) -> Option<WindowResponse<R>> {
+        if matches!(self.open.as_deref(), Some(false)) {
+            return None;
+        }
+        Some(ctx.unscrolled(|| self.show_inner(ctx, func)))
+    }
+
+    fn show_inner<R>(
+        mut self,
+        ctx: &Context,
+        func: impl FnOnce(&mut Ui) -> R,
+    ) -> WindowResponse<R> {
+        let screen = ctx.screen_rect();
+        let default_style = *ctx.style().borrow();
+        let style = self.style.unwrap_or(default_style.popup);
+        let title_width = str_width(&self.title, None) + 2;
+        let min_size = VecI2::new(
+            self.min_size.x.max(title_width.saturating_add(4)),
+            self.min_size.y.max(3),
+        );
+
+        let mut state = ctx.get_memory_or(
+            self.id,
+            WindowState {
+                pos: self.default_pos.unwrap_or_else(|| {
+                    VecI2::new(
+                        screen.width.saturating_sub(self.default_size.x) / 2,
+                        screen.height.saturating_sub(self.default_size.y) / 2,
+                    )
+                }),
+                size: self.default_size,
+                collapsed: false,
+                origin: Rect::default(),
+            },
+        );
+
+        // windows shown for the first time go in front, the order is kept
+        // across frames so clicking one only has to move it to the back of the list.
+        // Each window is kept with the frame it was last shown in
+        let order_id = Id::new("window order");
+        let frame = ctx.get_frame();
+        let mut order: Vec<(Id, usize)> = ctx.get_memory_or(order_id, Vec::new());
+        // windows not shown last frame or yet this frame were closed
+        order.retain(|(_, shown)| shown + 1 >= frame);
+        let index = match order.iter().position(|(id, _)| *id == self.id) {
+            Some(index) => {
+                order[index].1 = frame;
+                index
+            }
+            None => {
+                order.push((self.id, frame));
+                order.len() - 1
+            }
+        };
+        let layer = layers::WINDOW
+            .saturating_add(index.min(u8::MAX as usize) as u8)
+            .min(NonZeroU8::new(layers::MODAL.get() - 1).unwrap());
+
+        let rect = window_rect(&state, min_size, screen);
+        let mut ui = Ui::new(ctx.clone(), Layout::TopLeftVertical, self.id, rect, layer);
+
+        if let MouseButtonState::Down(pos) = ctx.input().mouse.buttons[0] {
+            if rect.contains(pos) && !ctx.is_covered(pos, layer) && index + 1 != order.len() {
+                let raised = order.remove(index);
+                order.push(raised);
+                ctx.request_redraw();
+            }
+        }
+        ctx.insert_into_memory(order_id, order);
+        ctx.register_layer_area(rect, layer);
+
+        // the buttons go first so the title bar does not take their clicks
+        let right = rect.x + rect.width - 1;
+        let collapse = self.collapsible.then(|| {
+            let area = Rect::new_pos_size(VecI2::new(rect.x + 1, rect.y), VecI2::new(1, 1));
+            (ui.interact(self.id.with("collapse"), area), area)
+        });
+        let close = self.open.is_some().then(|| {
+            let area = Rect::new_pos_size(VecI2::new(right - 1, rect.y), VecI2::new(1, 1));
+            (ui.interact(self.id.with("close"), area), area)
+        });
+        let title_bar = ui.interact_mouse(
+            self.id.with("title"),
+            Rect::new_pos_size(rect.top_left(), VecI2::new(rect.width, 1)),
+        );
+        if let Some((dx, dy)) = grab(&title_bar, &mut state, rect) {
+            state.pos = VecI2::new(
+                (state.origin.x as i32 + dx).max(0) as u16,
+                (state.origin.y as i32 + dy).max(0) as u16,
+            );
+        }
+
+        if self.resizable && !state.collapsed {
+            let bottom = rect.y + rect.height - 1;
+            let side = VecI2::new(1, rect.height.saturating_sub(2));
+            let corner = VecI2::new(1, 1);
+            let width = VecI2::new(rect.width.saturating_sub(2), 1);
+            let grips = [
+                ("left", VecI2::new(rect.x, rect.y + 1), side, Grip::LEFT),
+                ("right", VecI2::new(right, rect.y + 1), side, Grip::RIGHT),
+                (
+                    "bottom left",
+                    VecI2::new(rect.x, bottom),
+                    corner,
+                    Grip::BOTTOM_LEFT,
+                ),
+                (
+                    "bottom right",
+                    VecI2::new(right, bottom),
+                    corner,
+                    Grip::BOTTOM_RIGHT,
+                ),
+                (
+                    "bottom",
+                    VecI2::new(rect.x + 1, bottom),
+                    width,
+                    Grip::BOTTOM,
+                ),
+            ];
+            for (name, pos, size, grip) in grips {
+                let response = ui.interact_mouse(self.id.with(name), Rect::new_pos_size(pos, size));
+                if let Some(delta) = grab(&response, &mut state, rect) {
+                    let resized = resize(state.origin, grip, delta, min_size);
+                    state.pos = resized.top_left();
+                    state.size = resized.size();
+                }
+            }
+        }
+
+        if collapse
+            .as_ref()
+            .is_some_and(|(response, _)| response.clicked())
+        {
+            state.collapsed = !state.collapsed;
+            ctx.request_redraw();
+        }
+        if close
+            .as_ref()
+            .is_some_and(|(response, _)| response.clicked())
+        {
+            if let Some(open) = self.open.as_deref_mut() {
+                *open = false;
+            }
+            ctx.request_redraw();
+        }
+
+        // draw with where the window was at the start of the frame, anything
+        // moved this frame shows up next frame
+        fill_box(ctx, rect, layer, style);
+        if rect.height < 2 {
+            let lines = default_style.lines;
+            let line = format!(
+                "{}{}{}",
+                lines.top_left,
+                lines
+                    .horizontal
+                    .repeat(rect.width.saturating_sub(2) as usize),
+                lines.top_right
+            );
+            ctx.draw(&line, style, rect.top_left(), layer, rect);
+        } else {
+            draw_border(ctx, rect, layer, style);
+        }
+        let title_x = rect.x + if self.collapsible { 2 } else { 1 };
+        ctx.draw(
+            &format!(" {} ", self.title),
+            style,
+            VecI2::new(title_x, rect.y),
+            layer,
+            Rect::new_pos_size(
+                VecI2::new(title_x, rect.y),
+                VecI2::new(right.saturating_sub(title_x + 1), 1),
+            ),
+        );
+        let button_style = |response: &Response| {
+            if response.pressed() {
+                default_style.button_clicked
+            } else if response.hovered() {
+                default_style.button_hovered
+            } else {
+                style
+            }
+        };
+        if let Some((response, area)) = &collapse {
+            let pointers = default_style.pointers;
+            let glyph = if state.collapsed {
+                pointers.right
+            } else {
+                pointers.down
+            };
+            ctx.draw(glyph, button_style(response), area.top_left(), layer, *area);
+        }
+        if let Some((response, area)) = &close {
+            ctx.draw(
+                default_style.close,
+                button_style(response),
+                area.top_left(),
+                layer,
+                *area,
+            );
+        }
+
+        let inner = (!state.collapsed && rect.height > 2).then(|| {
+            let mut inner_rect = rect;
+            inner_rect.shrink_evenly(1);
+            let mut ui = Ui::new(
+                ctx.clone(),
+                Layout::TopLeftVertical,
+                self.id.with("window"),
+                inner_rect,
+                layer,
+            );
+            ctx.scrolled(inner_rect, VecI2::new(0, 0), || func(&mut ui))
+        });
+
+        if window_rect(&state, min_size, screen) != rect {
+            ctx.request_redraw();
+        }
+        let collapsed = state.collapsed;
+        ctx.insert_into_memory(self.id, state);
+        WindowResponse {
+            inner,
+            rect,
+            collapsed,
+        }
+    }
+}
+
+/// Where the window goes this frame, kept on the screen
+fn window_rect(state: &WindowState, min_size: VecI2, screen: Rect) -> Rect {
+    let size = VecI2::new(
+        state.size.x.max(min_size.x).min(screen.width),
+        if state.collapsed {
+            1
+        } else {
+            state.size.y.max(min_size.y).min(screen.height)
+        },
+    );
+    let pos = VecI2::new(
+        state.pos.x.min(screen.width.saturating_sub(size.x)),
+        state.pos.y.min(screen.height.saturating_sub(size.y)),
+    );
+    Rect::new_pos_size(pos, size)
+}
+
+/// Remembers where the window was when `response` is pressed and returns how
+/// far it has been dragged since
+fn grab(response: &Response, state: &mut WindowState, rect: Rect) -> Option<(i32, i32)> {
+    match response.buttons[0] {
+        MouseButtonState::Down(_) => {
+            state.origin = rect;
+            None
+        }
+        MouseButtonState::Drag { start, current } => Some((
+            current.x as i32 - start.x as i32,
+            current.y as i32 - start.y as i32,
+        )),
+        _ => None,
+    }
+}
+
+/// `origin` with the borders of `grip` moved by `delta`, the opposite borders stay put
+pub(crate) fn resize(origin: Rect, grip: Grip, (dx, dy): (i32, i32), min_size: VecI2) -> Rect {
+    let (mut x, mut width) = (origin.x as i32, origin.width as i32);
+    let min_width = min_size.x as i32;
+    if grip.left {
+        let right = x + width;
+        x = (x + dx).clamp(0, (right - min_width).max(0));
+        width = right - x;
+    }
+    if grip.right {
+        width = (width + dx).max(min_width);
+    }
+    let mut height = origin.height as i32;
+    if grip.bottom {
+        height = (height + dy).max(min_size.y as i32);
+    }
+    Rect::new_pos_size(
+        VecI2::new(x as u16, origin.y),
+        VecI2::new(
+            width.min(u16::MAX as i32) as u16,
+            height.min(u16::MAX as i32) as u16,
+        ),
+    )
+}
+
+#[test]
+pub fn test_window_resize() {
+    let origin = Rect::new_pos_size(VecI2::new(10, 5), VecI2::new(20, 8));
+    let min = VecI2::new(6, 3);
+    let rect = resize(origin, Grip::LEFT, (-4, 3), min);
+    assert_eq!(
+        rect,
+        Rect::new_pos_size(VecI2::new(6, 5), VecI2::new(24, 8))
+    );
+    // the right border stays put when shrinking past the minimum
+    let rect = resize(origin, Grip::LEFT, (30, 0), min);
+    assert_eq!(
+        rect,
+        Rect::new_pos_size(VecI2::new(24, 5), VecI2::new(6, 8))
+    );
+    let rect = resize(origin, Grip::LEFT, (-20, 0), min);
+    assert_eq!(
+        rect,
+        Rect::new_pos_size(VecI2::new(0, 5), VecI2::new(30, 8))
+    );
+
+    let rect = resize(origin, Grip::BOTTOM_RIGHT, (5, -10), min);
+    assert_eq!(
+        rect,
+        Rect::new_pos_size(VecI2::new(10, 5), VecI2::new(25, 3))
+    );
+}
@@ -1,13 +1,26 @@
 use std::{
-    cell::RefCell, cmp::Ordering, collections::HashMap, marker::PhantomData, num::NonZeroU8,
-    time::Duration,
+    any::Any,
+    cell::RefCell,
+    cmp::Ordering,
+    collections::HashMap,
+    marker::PhantomData,
+    num::NonZeroU8,
+    ops::Range,
+    rc::Rc,
+    time::{Duration, Instant},
 };
 
 use crossterm::event::Event;
 
 use crate::{
+    clipboard::Clipboard,
     id::Id,
-    input::{mouse::MouseButtonState, InputState, MoreInput},
+    input::{
+        mouse::MouseButtonState,
+        shortcut::{Shortcut, Shortcuts},
+        InputState, MoreInput,
+    },
+    layers,
     math_util::{Rect, VecI2},
     memory::Memory,
     response::Response,
@@ -16,12 +29,59 @@ use crate::{
     ui::{Layout, Ui},
 };
 
+/// Keys the focused widget wants for itself instead of moving focus
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FocusLock {
+    pub horizontal: bool,
+    pub vertical: bool,
+    pub tab: bool,
+}
+
 #[derive(Debug, Default)]
 pub struct Focus {
     focused: Option<(Id, Rect)>,
+    /// where the focused widget was laid out this frame and the viewport offset at the time
+    focused_area: Option<(Rect, VecI2)>,
     last_focused: Option<Id>,
     ids: HashMap<Id, (Rect, usize)>,
     ordered: Vec<Id>,
+    lock: FocusLock,
+    /// focus order positions focus may not leave, set again every frame by modals
+    trap: Option<Range<usize>>,
+}
+
+impl Focus {
+    pub fn focused(&self) -> Option<Id> {
+        self.focused.map(|(id, _)| id)
+    }
+
+    pub fn last_focused(&self) -> Option<Id> {
+        self.last_focused
+    }
+
+    /// How many focusable widgets were shown so far this frame
+    pub fn count(&self) -> usize {
+        self.ordered.len()
+    }
+
+    /// Position of the focused widget in this frames focus order
+    pub fn focused_index(&self) -> Option<usize> {
+        self.ids.get(&self.focused()?).map(|(_, index)| *index)
+    }
+}
+
+/// Translation from the coordinates widgets are laid out in to the screen and
+/// the part of the screen they may draw to, see [`Context::scrolled`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Viewport {
+    offset: VecI2,
+    clip: Option<Rect>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DragAndDrop {
+    pub source: Id,
+    pub payload: Rc<dyn Any>,
 }
 
 #[derive(Debug)]
@@ -43,15 +103,39 @@ pub struct ContextInner {
 
     used_ids: HashMap<Id, Rect>,
 
+    scroll_areas: Vec<(Id, Rect, NonZeroU8)>,
+    scroll_target: Option<Id>,
+    /// areas taken by popups and other higher layers, widgets below them do not get the mouse there
+    layer_areas: Vec<(Rect, NonZeroU8)>,
+    last_layer_areas: Vec<(Rect, NonZeroU8)>,
+    /// cells on layers below this are dimmed when the frame is output
+    dim_below: Option<NonZeroU8>,
+    viewport: Viewport,
+
+    /// the widget that got the mouse down, it keeps receiving the button
+    /// state until every button is released
+    captured: Option<Id>,
+
+    dnd: Option<DragAndDrop>,
+
+    /// ids hovered this frame and when their hover started
+    hovered: HashMap<Id, Instant>,
+    last_hovered: HashMap<Id, Instant>,
+
     pub(crate) min_tick_rate: Duration,
     pub(crate) max_tick_rate: Duration,
     pub(crate) request_redraw: bool,
+    pub(crate) redraw_at: Option<Instant>,
 
     pontees: usize,
     borrowed: bool,
     _phantom: PhantomData<*mut ()>,
 
     style: RefCell<DefaultStyle>,
+    shortcuts: RefCell<Shortcuts>,
+    clipboard: RefCell<Clipboard>,
+    /// indices into this frames key events that a widget already acted on
+    consumed_keys: Vec<usize>,
 
     focus: RefCell<Focus>,
 
@@ -81,13 +165,27 @@ impl ContextInner {
             resized: Default::default(),
             frame: Default::default(),
             used_ids: Default::default(),
+            scroll_areas: Default::default(),
+            scroll_target: Default::default(),
+            layer_areas: Default::default(),
+            last_layer_areas: Default::default(),
+            dim_below: Default::default(),
+            viewport: Default::default(),
+            captured: Default::default(),
+            dnd: Default::default(),
+            hovered: Default::default(),
+            last_hovered: Default::default(),
             min_tick_rate: Default::default(),
             max_tick_rate: Default::default(),
             request_redraw: Default::default(),
+            redraw_at: Default::default(),
             pontees: 0,
             borrowed: false,
             _phantom: PhantomData,
             style: RefCell::new(DefaultStyle::new_unicode()),
+            shortcuts: RefCell::default(),
+            clipboard: RefCell::default(),
+            consumed_keys: Vec::new(),
             current_cursor: None,
             last_cursor: None,
             focus: RefCell::default(),
@@ -116,29 +214,37 @@ impl ContextInner {
             Right,
         }
 
+        std::mem::swap(&mut self.hovered, &mut self.last_hovered);
+        self.hovered.clear();
+        self.consumed_keys.clear();
+        std::mem::swap(&mut self.layer_areas, &mut self.last_layer_areas);
+        self.layer_areas.clear();
+
         self.focus.get_mut().last_focused = self.focus.get_mut().focused.map(|v| v.0);
+        self.focus.get_mut().focused_area = None;
 
         let mut direction = Direction::None;
         {
             use crossterm::event::KeyCode;
+            let lock = std::mem::take(&mut self.focus.get_mut().lock);
             let pressed = &self.input.keyboard.pressed;
-            if pressed.get(&KeyCode::Tab).is_some() {
+            if !lock.tab && pressed.contains_key(&KeyCode::Tab) {
                 direction = Direction::Forward;
             }
-            if pressed.get(&KeyCode::BackTab).is_some() {
+            if !lock.tab && pressed.contains_key(&KeyCode::BackTab) {
                 direction = Direction::Backward;
             }
 
-            if pressed.get(&KeyCode::Up).is_some() {
+            if !lock.vertical && pressed.contains_key(&KeyCode::Up) {
                 direction = Direction::Up;
             }
-            if pressed.get(&KeyCode::Down).is_some() {
+            if !lock.vertical && pressed.contains_key(&KeyCode::Down) {
                 direction = Direction::Down;
             }
-            if pressed.get(&KeyCode::Left).is_some() {
+            if !lock.horizontal && pressed.contains_key(&KeyCode::Left) {
                 direction = Direction::Left;
             }
-            if pressed.get(&KeyCode::Right).is_some() {
+            if !lock.horizontal && pressed.contains_key(&KeyCode::Right) {
                 direction = Direction::Right;
             }
         }
@@ -149,20 +255,45 @@ impl ContextInner {
             }
         }
 
+        // a modal keeps focus inside of it, pulling it in when it is somewhere else
+        let focus = self.focus.get_mut();
+        let count = focus.ordered.len();
+        let trapped = focus.trap.take();
+        let trap = trapped
+            .clone()
+            .map_or(0..count, |trap| trap.start.min(count)..trap.end.min(count));
+        if trap.is_empty() {
+            focus.focused = None;
+        } else if trapped.is_some() {
+            let inside = focus
+                .focused()
+                .and_then(|id| focus.ids.get(&id))
+                .is_some_and(|(_, index)| trap.contains(index));
+            if !inside {
+                let first = focus.ordered[trap.start];
+                focus.focused = focus.ids.get(&first).map(|(rect, _)| (first, *rect));
+                direction = Direction::None;
+            }
+        }
+
         let id = if let Some((focused_id, focused_rect)) = self.focus.get_mut().focused {
             match direction {
                 Direction::None => None,
                 Direction::Forward | Direction::Backward => {
-                    let n: usize = self.focus.get_mut().ordered.len();
+                    let n = trap.len();
                     let direction = if direction == Direction::Forward {
                         1
                     } else {
                         -1
                     };
                     (|| {
-                        let index = self.focus.get_mut().ids.get(&focused_id)?.1;
+                        let index = self.focus.get_mut().ids.get(&focused_id)?.1 - trap.start;
                         let index = index.checked_add_signed(direction).unwrap_or(n - 1) % n;
-                        self.focus.get_mut().ordered.get(index).copied()
+                        self.focus
+                            .get_mut()
+                            .ordered
+                            .get(trap.start + index)
+                            .copied()
                     })()
                 }
                 Direction::Up | Direction::Down | Direction::Left | Direction::Right => {
@@ -195,8 +326,8 @@ impl ContextInner {
                     let (cx, cy) = find_edge(edge, focused_rect);
 
                     let mut closest: Option<(Id, (f32, f32))> = None;
-                    for (id, (rect, _)) in self.focus.borrow_mut().ids.iter() {
-                        if focused_id == *id {
+                    for (id, (rect, index)) in self.focus.borrow_mut().ids.iter() {
+                        if focused_id == *id || !trap.contains(index) {
                             continue;
                         }
 
@@ -221,15 +352,13 @@ impl ContextInner {
 
                         if let Some((_, (cpd, csd))) = closest {
                             match pd.partial_cmp(&cpd) {
-                                Some(Ordering::Equal) => {
-                                    if sd < csd {
-                                        closest = Some((*id, (pd, sd)));
-                                    }
+                                Some(Ordering::Equal) if sd < csd => {
+                                    closest = Some((*id, (pd, sd)));
                                 }
-                                Some(Ordering::Less | Ordering::Greater) => {
-                                    if sd * sd * 1.5 + pd * pd < csd * csd * 1.5 + cpd * cpd {
-                                        closest = Some((*id, (pd, sd)));
-                                    }
+                                Some(Ordering::Less | Ordering::Greater)
+                                    if sd * sd * 1.5 + pd * pd < csd * csd * 1.5 + cpd * cpd =>
+                                {
+                                    closest = Some((*id, (pd, sd)));
                                 }
                                 _ => {}
                             }
@@ -242,7 +371,7 @@ impl ContextInner {
                 }
             }
         } else if direction != Direction::None {
-            self.focus.get_mut().ordered.first().copied()
+            self.focus.get_mut().ordered.get(trap.start).copied()
         } else {
             None
         };
@@ -254,9 +383,30 @@ impl ContextInner {
         // eprintln!("{:?}", self.focus.get_mut().ids);
         self.focus.get_mut().ids.clear();
         self.focus.get_mut().ordered.clear();
+
+        // scrolling goes to the innermost scrollable region under the mouse last frame,
+        // higher layers win and then the smallest area containing the mouse
+        self.scroll_target = None;
+        if let Some(position) = self.input.mouse.position {
+            self.scroll_target = self
+                .scroll_areas
+                .iter()
+                .filter(|(_, rect, _)| rect.contains(position))
+                .max_by(|(_, r1, l1), (_, r2, l2)| {
+                    l1.cmp(l2).then_with(|| {
+                        (r2.width as u32 * r2.height as u32)
+                            .cmp(&(r1.width as u32 * r1.height as u32))
+                    })
+                })
+                .map(|(id, _, _)| *id);
+        }
+        self.scroll_areas.clear();
     }
 
     pub fn get_finished_frame(&mut self) -> FinishedFrame<'_> {
+        if let Some(layer) = self.dim_below.take() {
+            self.current.dim_below(layer);
+        }
         FinishedFrame {
             resized: self.resized,
             // we want to preserve this frame to allow us to diff it next frame
@@ -265,6 +415,7 @@ impl ContextInner {
             last_frame: self.last.drain(),
             current_cursor: self.current_cursor,
             last_cursor: self.last_cursor,
+            clipboard: self.clipboard.get_mut().take_pending(),
         }
     }
 
@@ -280,6 +431,14 @@ impl ContextInner {
         std::mem::swap(last, current);
         self.resized = false;
 
+        if self.input.mouse.buttons.iter().all(|button| button.is_up()) {
+            self.captured = None;
+        }
+        // drop targets get the payload on the frame the button is released
+        if self.input.mouse.buttons[0].is_up() {
+            self.dnd = None;
+        }
+
         let more_input = self.input.next_state();
 
         self.used_ids.clear();
@@ -316,6 +475,8 @@ pub struct FinishedFrame<'a> {
     pub last_frame: ScreenDrain<'a>,
     pub current_cursor: Option<Cursor>,
     pub last_cursor: Option<Cursor>,
+    /// escape sequence setting the system clipboard
+    pub clipboard: Option<String>,
 }
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -394,7 +555,7 @@ impl Context {
             Layout::TopLeftVertical,
             Id::new("frame"),
             clip,
-            NonZeroU8::new(128).unwrap(),
+            layers::FRAME,
         ));
     }
 
@@ -408,10 +569,48 @@ impl Context {
         unsafe { &(*self.inner).focus }
     }
 
+    pub fn is_focused(&self, id: Id) -> bool {
+        self.focus().borrow().focused() == Some(id)
+    }
+
+    pub fn request_focus(&self, id: Id, rect: Rect) {
+        self.focus().borrow_mut().focused = Some((id, self.to_screen(rect)));
+    }
+
+    pub fn surrender_focus(&self, id: Id) {
+        let mut focus = self.focus().borrow_mut();
+        if focus.focused() == Some(id) {
+            focus.focused = None;
+        }
+    }
+
+    /// Stops the given keys from moving focus next frame, call it every frame
+    /// the widget is focused and needs them
+    pub fn lock_focus(&self, lock: FocusLock) {
+        let current = &mut self.focus().borrow_mut().lock;
+        current.horizontal |= lock.horizontal;
+        current.vertical |= lock.vertical;
+        current.tab |= lock.tab;
+    }
+
+    /// Keeps focus within the widgets at `range` in this frames focus order next
+    /// frame, see [`Focus::count`]
+    pub fn trap_focus(&self, range: Range<usize>) {
+        self.focus().borrow_mut().trap = Some(range);
+    }
+
     pub fn request_redraw(&self) {
         unsafe { (*self.inner).request_redraw = true }
     }
 
+    /// Requests a redraw once `duration` has elapsed even if no input arrives,
+    /// useful for delayed effects like tooltips
+    pub fn request_redraw_after(&self, duration: Duration) {
+        let at = Instant::now() + duration;
+        let redraw_at = unsafe { &mut (*self.inner).redraw_at };
+        *redraw_at = Some(redraw_at.map_or(at, |current| current.min(at)));
+    }
+
     pub fn should_redraw(&self) -> bool {
         unsafe { (*self.inner).request_redraw }
     }
@@ -432,6 +631,11 @@ impl Context {
         unsafe { (*self.inner).max_tick_rate = duration }
     }
 
+    /// Limits how many bytes of a single bracketed paste are kept
+    pub fn set_max_paste_len(&self, len: usize) {
+        unsafe { (*self.inner).input.keyboard.max_paste_len = len }
+    }
+
     pub fn previous_frame_report(&self) -> PreviousFrameReport {
         unsafe { (*self.inner).previous_frame_report }
     }
@@ -440,12 +644,65 @@ impl Context {
         unsafe { &(*self.inner).style }
     }
 
+    pub fn shortcuts(&self) -> &RefCell<Shortcuts> {
+        unsafe { &(*self.inner).shortcuts }
+    }
+
+    pub fn clipboard(&self) -> &RefCell<Clipboard> {
+        unsafe { &(*self.inner).clipboard }
+    }
+
+    /// Puts `text` on the clipboard
+    pub fn copy_text(&self, text: impl Into<String>) {
+        self.clipboard().borrow_mut().set(text)
+    }
+
+    /// Marks the key event at `index` in this frames key events as handled
+    pub fn consume_key(&self, index: usize) {
+        unsafe { (*self.inner).consumed_keys.push(index) }
+    }
+
+    pub fn is_key_consumed(&self, index: usize) -> bool {
+        unsafe { (*self.inner).consumed_keys.contains(&index) }
+    }
+
+    /// Whether any of `shortcuts` was pressed this frame and not yet handled by
+    /// a widget, consumes the key if so
+    pub fn consume_shortcut(&self, shortcuts: &[Shortcut]) -> bool {
+        let index = self
+            .input()
+            .keyboard
+            .events
+            .iter()
+            .enumerate()
+            .position(|(i, key)| {
+                !self.is_key_consumed(i) && shortcuts.iter().any(|s| s.matches(key))
+            });
+        if let Some(index) = index {
+            self.consume_key(index);
+        }
+        index.is_some()
+    }
+
     pub fn get_cursor(&self) -> Option<Cursor> {
         unsafe { (*self.inner).current_cursor }
     }
 
     pub fn set_cursor(&self, cursor: Cursor) {
-        unsafe { (*self.inner).current_cursor = Some(cursor) }
+        let viewport = unsafe { (*self.inner).viewport };
+        let (Some(x), Some(y)) = (
+            cursor.x.checked_sub(viewport.offset.x),
+            cursor.y.checked_sub(viewport.offset.y),
+        ) else {
+            return;
+        };
+        if viewport
+            .clip
+            .is_some_and(|clip| !clip.contains(VecI2::new(x, y)))
+        {
+            return;
+        }
+        unsafe { (*self.inner).current_cursor = Some(Cursor { x, y }) }
     }
 
     /// Creates a new [`Context`].
@@ -463,6 +720,32 @@ impl Context {
     }
 
     pub fn draw(&self, str: &str, style: Style, start: VecI2, layer: NonZeroU8, clip: Rect) {
+        let viewport = unsafe { (*self.inner).viewport };
+        if viewport == Viewport::default() {
+            unsafe {
+                (*self.inner)
+                    .current
+                    .push_text(str, style, start, layer, clip)
+            }
+            return;
+        }
+
+        let offset = viewport.offset;
+        let clip = self.visible_rect(clip);
+        if start.y < offset.y || clip.width == 0 || clip.height == 0 {
+            return;
+        }
+        // skip whatever is scrolled out to the left
+        let mut str = str;
+        let mut x = start.x;
+        while x < offset.x {
+            let Some(char) = str.chars().next() else {
+                return;
+            };
+            x = x.saturating_add(unicode_width::UnicodeWidthChar::width(char).unwrap_or(0) as u16);
+            str = &str[char.len_utf8()..];
+        }
+        let start = VecI2::new(x - offset.x, start.y - offset.y);
         unsafe {
             (*self.inner)
                 .current
@@ -470,37 +753,187 @@ impl Context {
         }
     }
 
-    pub fn interact(&self, _clip: Rect, _layer: NonZeroU8, id: Id, area: Rect) -> Response {
+    /// Runs `func` with everything laid out `scroll` further down and right than it
+    /// shows on screen, clipped to `clip` given in the current coordinates. Mouse
+    /// positions are translated the same way so widgets inside do not need to care
+    pub fn scrolled<R>(&self, clip: Rect, scroll: VecI2, func: impl FnOnce() -> R) -> R {
+        let old = unsafe { (*self.inner).viewport };
+        self.set_viewport(Viewport {
+            offset: old.offset + scroll,
+            clip: Some(self.visible_rect(clip)),
+        });
+        let res = func();
+        self.set_viewport(old);
+        res
+    }
+
+    /// Runs `func` in plain screen coordinates without any clip, for things
+    /// floating above scrolled content like popups
+    pub fn unscrolled<R>(&self, func: impl FnOnce() -> R) -> R {
+        let old = unsafe { (*self.inner).viewport };
+        self.set_viewport(Viewport::default());
+        let res = func();
+        self.set_viewport(old);
+        res
+    }
+
+    fn set_viewport(&self, viewport: Viewport) {
+        let inner = unsafe { &mut *self.inner };
+        let (old, new) = (inner.viewport.offset, viewport.offset);
+        inner
+            .input
+            .mouse
+            .map_positions(|pos| VecI2::new(pos.x - old.x + new.x, pos.y - old.y + new.y));
+        inner.viewport = viewport;
+    }
+
+    /// How far the current coordinates are scrolled from the screen
+    pub fn viewport_offset(&self) -> VecI2 {
+        unsafe { (*self.inner).viewport.offset }
+    }
+
+    /// `rect` moved from the current coordinates to the screen, parts scrolled
+    /// past the top left edge of the screen are cut off
+    pub fn to_screen(&self, rect: Rect) -> Rect {
+        let offset = self.viewport_offset();
+        Rect::new_pos_pos(
+            VecI2::new(
+                rect.x.saturating_sub(offset.x),
+                rect.y.saturating_sub(offset.y),
+            ),
+            VecI2::new(
+                rect.x.saturating_add(rect.width).saturating_sub(offset.x),
+                rect.y.saturating_add(rect.height).saturating_sub(offset.y),
+            ),
+        )
+    }
+
+    /// The part of `rect` that is visible on screen, in screen coordinates
+    pub fn visible_rect(&self, rect: Rect) -> Rect {
+        let mut rect = self.to_screen(rect);
+        if let Some(clip) = unsafe { (*self.inner).viewport.clip } {
+            rect.shrink_to_fit_within(clip);
+        }
+        rect
+    }
+
+    /// Where the focused widget was laid out this frame, in the current coordinates
+    pub fn focused_area(&self) -> Option<Rect> {
+        let (mut area, offset) = self.focus().borrow().focused_area?;
+        let current = self.viewport_offset();
+        area.x = (area.x + current.x).checked_sub(offset.x)?;
+        area.y = (area.y + current.y).checked_sub(offset.y)?;
+        Some(area)
+    }
+
+    /// Marks `area` as covered by `layer` so widgets on lower layers stop receiving
+    /// the mouse there, this frame and the next
+    pub fn register_layer_area(&self, area: Rect, layer: NonZeroU8) {
+        let area = self.visible_rect(area);
+        unsafe { (*self.inner).layer_areas.push((area, layer)) }
+    }
+
+    /// Dims everything drawn below `layer` this frame, to set a modal apart from the rest
+    pub fn dim_below(&self, layer: NonZeroU8) {
+        let dim_below = unsafe { &mut (*self.inner).dim_below };
+        *dim_below = (*dim_below).max(Some(layer));
+    }
+
+    /// Whether something on a layer above `layer` covers `pos`, or `pos` is
+    /// outside of the visible part of a scroll area
+    pub fn is_covered(&self, pos: VecI2, layer: NonZeroU8) -> bool {
+        let inner = unsafe { &*self.inner };
+        let offset = inner.viewport.offset;
+        let pos = VecI2::new(
+            pos.x.saturating_sub(offset.x),
+            pos.y.saturating_sub(offset.y),
+        );
+        if inner.viewport.clip.is_some_and(|clip| !clip.contains(pos)) {
+            return true;
+        }
+        inner
+            .layer_areas
+            .iter()
+            .chain(&inner.last_layer_areas)
+            .any(|(area, above)| *above > layer && area.contains(pos))
+    }
+
+    pub fn interact(&self, _clip: Rect, layer: NonZeroU8, id: Id, area: Rect) -> Response {
+        self.interact_inner(layer, id, area, true)
+    }
+
+    /// Like [`Context::interact`] but the widget is not a focus stop, for handles
+    /// like title bars and resize grips that are only used with the mouse
+    pub fn interact_mouse(&self, _clip: Rect, layer: NonZeroU8, id: Id, area: Rect) -> Response {
+        self.interact_inner(layer, id, area, false)
+    }
+
+    fn interact_inner(&self, layer: NonZeroU8, id: Id, area: Rect, focusable: bool) -> Response {
         self.check_for_id_clash(id, area);
-        self.push_id(id, area);
 
         let mut focused = false;
-        if let Some((cid, crect)) = &mut self.focus().borrow_mut().focused {
-            if id == *cid {
-                *crect = area;
-                focused = true;
+        if focusable {
+            let screen_area = self.to_screen(area);
+            self.push_id(id, screen_area);
+
+            let mut focus = self.focus().borrow_mut();
+            if let Some((cid, crect)) = &mut focus.focused {
+                if id == *cid {
+                    *crect = screen_area;
+                    focused = true;
+                }
+            }
+            if focused {
+                focus.focused_area = Some((area, self.viewport_offset()));
             }
         }
 
-        let mut response = if let Some(position) = &self.input().mouse.position {
-            if area.contains(*position) {
-                let mut response = Response::new(area, id, Some(*position));
-                response.buttons = self.input().mouse.buttons;
-                response
-            } else {
-                Response::new(area, id, None)
+        let inner = unsafe { &mut *self.inner };
+        let mouse = &inner.input.mouse;
+        let mut response = Response::new(area, id, mouse.position);
+        if let Some(position) = mouse.position {
+            let captured = inner.captured == Some(id);
+            let covered = self.is_covered(position, layer);
+            response.hovered &= !covered;
+            if captured || (inner.captured.is_none() && !covered && area.contains(position)) {
+                response.buttons = mouse.buttons;
+                if response
+                    .buttons
+                    .iter()
+                    .any(|b| matches!(b, MouseButtonState::Down(_)))
+                {
+                    inner.captured = Some(id);
+                }
             }
-        } else {
-            Response::new(area, id, None)
-        };
+            if captured {
+                response.drag_started = mouse.drag_started[0];
+                if response.dragged() {
+                    response.drag_delta = mouse.delta();
+                }
+            }
+        }
         response.hovered |= focused;
+        response.focused = focused;
+
+        let last_hover = inner.last_hovered.get(&id).copied();
+        if response.hovered {
+            let now = Instant::now();
+            let start = last_hover.unwrap_or(now);
+            inner.hovered.insert(id, start);
+            response.hover_started = last_hover.is_none();
+            response.hover_duration = now.duration_since(start);
+        } else {
+            response.hover_ended = last_hover.is_some();
+        }
+        if focused {
+            response.paste.clone_from(&self.input().keyboard.paste);
+        }
         if focused
             && self
                 .input()
                 .keyboard
                 .pressed
-                .get(&crossterm::event::KeyCode::Enter)
-                .is_some()
+                .contains_key(&crossterm::event::KeyCode::Enter)
         {
             self.request_redraw();
             response.buttons[0] = MouseButtonState::Down(area.top_left());
@@ -508,6 +941,70 @@ impl Context {
         response
     }
 
+    /// The widget currently holding the pointer capture
+    pub fn captured(&self) -> Option<Id> {
+        unsafe { (*self.inner).captured }
+    }
+
+    /// Like [`Context::interact`] but also marks `area` as a scrollable region.
+    /// The wheel delta is only reported to the innermost scrollable region
+    /// that was under the mouse
+    pub fn interact_scroll(&self, _clip: Rect, layer: NonZeroU8, id: Id, area: Rect) -> Response {
+        self.interact_scroll_inner(layer, id, area, true)
+    }
+
+    /// Like [`Context::interact_scroll`] but not a focus stop, see [`Context::interact_mouse`]
+    pub fn interact_scroll_mouse(
+        &self,
+        _clip: Rect,
+        layer: NonZeroU8,
+        id: Id,
+        area: Rect,
+    ) -> Response {
+        self.interact_scroll_inner(layer, id, area, false)
+    }
+
+    fn interact_scroll_inner(
+        &self,
+        layer: NonZeroU8,
+        id: Id,
+        area: Rect,
+        focusable: bool,
+    ) -> Response {
+        let visible = self.visible_rect(area);
+        unsafe { (*self.inner).scroll_areas.push((id, visible, layer)) };
+        let mut response = self.interact_inner(layer, id, area, focusable);
+        if unsafe { (*self.inner).scroll_target } == Some(id) {
+            response.delta_scroll_x = self.input().mouse.delta_scroll_x;
+            response.delta_scroll_y = self.input().mouse.delta_scroll_y;
+        }
+        response
+    }
+
+    /// Starts a drag and drop carrying `payload`, it is dropped once the
+    /// primary mouse button is released
+    pub fn dnd_set_payload<T: Any>(&self, source: Id, payload: T) {
+        unsafe {
+            (*self.inner).dnd = Some(DragAndDrop {
+                source,
+                payload: Rc::new(payload),
+            })
+        }
+    }
+
+    pub fn dnd_payload<T: Any>(&self) -> Option<Rc<T>> {
+        let dnd = unsafe { (*self.inner).dnd.as_ref() }?;
+        dnd.payload.clone().downcast().ok()
+    }
+
+    pub fn dnd_source(&self) -> Option<Id> {
+        unsafe { (*self.inner).dnd.as_ref().map(|dnd| dnd.source) }
+    }
+
+    pub fn dnd_clear(&self) {
+        unsafe { (*self.inner).dnd = None }
+    }
+
     pub fn insert_into_memory<T: Clone + 'static>(&self, id: Id, val: T) {
         unsafe { (*self.inner).memory.insert(id, val) };
     }
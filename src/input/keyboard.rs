@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crossterm::event::{KeyCode, KeyEventState, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventState, KeyModifiers};
 
 use crate::ui::Ui;
 
@@ -8,16 +8,52 @@ use super::MoreInput;
 
 // type Key = crossterm::event::KeyCode;
 
-#[derive(Debug, Default, Clone)]
+/// Pastes larger than this are truncated, a few MiB of text in a single frame
+/// will stall layout for any text widget that receives it
+pub const DEFAULT_MAX_PASTE_LEN: usize = 1024 * 1024;
+
+#[derive(Debug, Clone)]
 pub struct KeyboardState {
     pub frame_input: String,
 
     pub pressed: HashMap<KeyCode, (KeyModifiers, KeyEventState)>,
+    /// every key press/repeat this frame in the order they arrived
+    pub events: Vec<KeyEvent>,
+
+    /// Text received through a bracketed paste this frame
+    pub paste: Option<String>,
+    /// Set when `paste` was cut short by `max_paste_len`
+    pub paste_truncated: bool,
+    pub max_paste_len: usize,
+}
+
+impl Default for KeyboardState {
+    fn default() -> Self {
+        Self {
+            frame_input: Default::default(),
+            pressed: Default::default(),
+            events: Default::default(),
+            paste: None,
+            paste_truncated: false,
+            max_paste_len: DEFAULT_MAX_PASTE_LEN,
+        }
+    }
 }
 
 impl KeyboardState {
     pub fn ui(&self, ui: &mut Ui) {
         ui.label(format!("frame input: {:?}", self.frame_input));
+        if let Some(paste) = &self.paste {
+            ui.label(format!(
+                "paste: {} bytes{}",
+                paste.len(),
+                if self.paste_truncated {
+                    " (truncated)"
+                } else {
+                    ""
+                }
+            ));
+        }
         ui.label("pressed");
         ui.add_horizontal_space(1);
         for (key, (modifier, state)) in self.pressed.iter() {
@@ -28,6 +64,9 @@ impl KeyboardState {
     pub fn next_state(&mut self) -> MoreInput {
         self.frame_input.clear();
         self.pressed.clear();
+        self.events.clear();
+        self.paste = None;
+        self.paste_truncated = false;
         MoreInput::Yes
     }
 
@@ -35,9 +74,26 @@ impl KeyboardState {
         &self.frame_input
     }
 
+    pub fn get_paste(&self) -> Option<&str> {
+        self.paste.as_deref()
+    }
+
     pub fn handle_paste(&mut self, paste: &str) -> MoreInput {
-        self.frame_input.push_str(paste);
-        MoreInput::Yes
+        let paste_buf = self.paste.get_or_insert_with(String::new);
+        let remaining = self.max_paste_len.saturating_sub(paste_buf.len());
+
+        if paste.len() > remaining {
+            let mut end = remaining;
+            while !paste.is_char_boundary(end) {
+                end -= 1;
+            }
+            paste_buf.push_str(&paste[..end]);
+            self.paste_truncated = true;
+        } else {
+            paste_buf.push_str(paste);
+        }
+        // give the paste its own frame so keys typed after it aren't applied first
+        MoreInput::No
     }
 
     pub fn handle_key(&mut self, key: crossterm::event::KeyEvent) -> MoreInput {
@@ -51,12 +107,18 @@ impl KeyboardState {
                     KeyCode::Tab => {
                         self.frame_input.push('\t');
                     }
-                    KeyCode::Char(char) => {
+                    // ctrl/alt + char are shortcuts not text
+                    KeyCode::Char(char)
+                        if !key
+                            .modifiers
+                            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+                    {
                         self.frame_input.push(char);
                     }
                     _ => {}
                 }
                 self.pressed.insert(key.code, (key.modifiers, key.state));
+                self.events.push(key);
             }
             KeyEventKind::Release => {}
         }
@@ -4,6 +4,7 @@ use self::{keyboard::KeyboardState, mouse::MouseState};
 
 mod keyboard;
 pub mod mouse;
+pub mod shortcut;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MoreInput {
@@ -8,21 +8,22 @@ pub mod mouse_buttons {
     pub static SECONDARY: usize = 2;
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum MouseButtonState {
+    #[default]
     UnPressed,
     Down(VecI2),
     Held(VecI2),
     Released(VecI2),
 
-    Drag { start: VecI2, current: VecI2 },
-    DragReleased { start: VecI2, released: VecI2 },
-}
-
-impl Default for MouseButtonState {
-    fn default() -> Self {
-        Self::UnPressed
-    }
+    Drag {
+        start: VecI2,
+        current: VecI2,
+    },
+    DragReleased {
+        start: VecI2,
+        released: VecI2,
+    },
 }
 
 impl MouseButtonState {
@@ -86,6 +87,24 @@ impl MouseButtonState {
         }
     }
 
+    /// Applies `func` to every position stored in the state
+    pub fn map_positions(&mut self, mut func: impl FnMut(VecI2) -> VecI2) {
+        match self {
+            MouseButtonState::UnPressed => {}
+            MouseButtonState::Down(pos)
+            | MouseButtonState::Held(pos)
+            | MouseButtonState::Released(pos) => *pos = func(*pos),
+            MouseButtonState::Drag { start, current } => {
+                *start = func(*start);
+                *current = func(*current);
+            }
+            MouseButtonState::DragReleased { start, released } => {
+                *start = func(*start);
+                *released = func(*released);
+            }
+        }
+    }
+
     pub fn button_down(&mut self, pos: VecI2) {
         match *self {
             MouseButtonState::UnPressed => *self = MouseButtonState::Down(pos),
@@ -99,13 +118,35 @@ impl MouseButtonState {
     }
 }
 
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Clone, Copy)]
 pub struct MouseState {
     pub position: Option<VecI2>,
     pub buttons: [MouseButtonState; 3],
     pub delta_scroll_x: i16,
     pub delta_scroll_y: i16,
     pub changed: bool,
+
+    /// mouse position at the end of the previous frame
+    pub prev_position: Option<VecI2>,
+    /// set for a button when it went from held to dragging this frame
+    pub drag_started: [bool; 3],
+    /// modifier keys held during the last mouse event
+    pub modifiers: crossterm::event::KeyModifiers,
+}
+
+impl Default for MouseState {
+    fn default() -> Self {
+        Self {
+            position: None,
+            buttons: Default::default(),
+            delta_scroll_x: 0,
+            delta_scroll_y: 0,
+            changed: false,
+            prev_position: None,
+            drag_started: [false; 3],
+            modifiers: crossterm::event::KeyModifiers::NONE,
+        }
+    }
 }
 
 impl MouseState {
@@ -142,23 +183,47 @@ impl MouseState {
         self.changed = !Into::<bool>::into(more_input);
         self.delta_scroll_x = 0;
         self.delta_scroll_y = 0;
+        self.prev_position = self.position;
+        self.drag_started = [false; 3];
         more_input
     }
 
+    /// Applies `func` to the current and previous position and those of every button
+    pub fn map_positions(&mut self, mut func: impl FnMut(VecI2) -> VecI2) {
+        self.position = self.position.map(&mut func);
+        self.prev_position = self.prev_position.map(&mut func);
+        for button in &mut self.buttons {
+            button.map_positions(&mut func);
+        }
+    }
+
+    /// How far the mouse moved since the end of the previous frame
+    pub fn delta(&self) -> (i16, i16) {
+        match (self.position, self.prev_position) {
+            (Some(current), Some(prev)) => (
+                current.x as i16 - prev.x as i16,
+                current.y as i16 - prev.y as i16,
+            ),
+            _ => (0, 0),
+        }
+    }
+
     pub fn handle_event(&mut self, event: crossterm::event::MouseEvent) -> MoreInput {
         self.changed = true;
         use crossterm::event::*;
         let event_pos = VecI2::new(event.column, event.row);
         self.position = Some(event_pos);
+        self.modifiers = event.modifiers;
         match event.kind {
             MouseEventKind::Down(button)
             | MouseEventKind::Up(button)
             | MouseEventKind::Drag(button) => {
-                let button = match button {
-                    MouseButton::Left => &mut self.buttons[0],
-                    MouseButton::Right => &mut self.buttons[2],
-                    MouseButton::Middle => &mut self.buttons[1],
+                let index = match button {
+                    MouseButton::Left => 0,
+                    MouseButton::Right => 2,
+                    MouseButton::Middle => 1,
                 };
+                let button = &mut self.buttons[index];
                 match event.kind {
                     MouseEventKind::Down(_) => {
                         button.button_down(event_pos);
@@ -169,6 +234,7 @@ impl MouseState {
                         MoreInput::No
                     }
                     MouseEventKind::Drag(_) => {
+                        self.drag_started[index] |= matches!(button, MouseButtonState::Held(_));
                         button.button_dragged(event_pos);
                         MoreInput::Yes
                     }
@@ -176,20 +242,30 @@ impl MouseState {
                 }
             }
             MouseEventKind::Moved => MoreInput::Yes,
-            MouseEventKind::ScrollDown => {
+            // shift + wheel scrolls horizontally, most terminals don't report
+            // horizontal wheel events on their own
+            MouseEventKind::ScrollDown if event.modifiers.contains(KeyModifiers::SHIFT) => {
+                self.delta_scroll_x += 1;
+                MoreInput::Yes
+            }
+            MouseEventKind::ScrollUp if event.modifiers.contains(KeyModifiers::SHIFT) => {
                 self.delta_scroll_x -= 1;
                 MoreInput::Yes
             }
+            MouseEventKind::ScrollDown => {
+                self.delta_scroll_y -= 1;
+                MoreInput::Yes
+            }
             MouseEventKind::ScrollUp => {
-                self.delta_scroll_x += 1;
+                self.delta_scroll_y += 1;
                 MoreInput::Yes
             }
             MouseEventKind::ScrollLeft => {
-                self.delta_scroll_y -= 1;
+                self.delta_scroll_x -= 1;
                 MoreInput::Yes
             }
             MouseEventKind::ScrollRight => {
-                self.delta_scroll_y += 1;
+                self.delta_scroll_x += 1;
                 MoreInput::Yes
             }
         }
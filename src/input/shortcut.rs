@@ -0,0 +1,91 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// A key combined with the exact modifiers that have to be held
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    pub modifiers: KeyModifiers,
+    pub code: KeyCode,
+}
+
+impl Shortcut {
+    pub const fn new(modifiers: KeyModifiers, code: KeyCode) -> Self {
+        Self { modifiers, code }
+    }
+
+    pub const fn ctrl(c: char) -> Self {
+        Self::new(KeyModifiers::CONTROL, KeyCode::Char(c))
+    }
+
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        // terminals disagree on whether shift upper cases the char
+        let code_matches = match (self.code, key.code) {
+            (KeyCode::Char(a), KeyCode::Char(b)) => a.eq_ignore_ascii_case(&b),
+            (a, b) => a == b,
+        };
+        code_matches && self.modifiers == key.modifiers
+    }
+}
+
+/// Whether any of `shortcuts` matches `key`
+pub fn any_matches(shortcuts: &[Shortcut], key: &KeyEvent) -> bool {
+    shortcuts.iter().any(|shortcut| shortcut.matches(key))
+}
+
+/// The key bindings used by the built in widgets, every action can have several
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shortcuts {
+    pub undo: Vec<Shortcut>,
+    pub redo: Vec<Shortcut>,
+    pub copy: Vec<Shortcut>,
+    pub cut: Vec<Shortcut>,
+    pub paste: Vec<Shortcut>,
+    /// opens the context menu of the focused widget
+    pub context_menu: Vec<Shortcut>,
+}
+
+impl Default for Shortcuts {
+    fn default() -> Self {
+        Self {
+            undo: vec![Shortcut::ctrl('z')],
+            redo: vec![
+                Shortcut::ctrl('y'),
+                Shortcut::new(
+                    KeyModifiers::CONTROL.union(KeyModifiers::SHIFT),
+                    KeyCode::Char('z'),
+                ),
+            ],
+            // plain ctrl+c quits the app
+            copy: vec![
+                Shortcut::new(
+                    KeyModifiers::CONTROL.union(KeyModifiers::SHIFT),
+                    KeyCode::Char('c'),
+                ),
+                Shortcut::new(KeyModifiers::CONTROL, KeyCode::Insert),
+            ],
+            cut: vec![
+                Shortcut::ctrl('x'),
+                Shortcut::new(KeyModifiers::SHIFT, KeyCode::Delete),
+            ],
+            paste: vec![
+                Shortcut::ctrl('v'),
+                Shortcut::new(KeyModifiers::SHIFT, KeyCode::Insert),
+            ],
+            context_menu: vec![
+                Shortcut::new(KeyModifiers::SHIFT, KeyCode::F(10)),
+                Shortcut::new(KeyModifiers::NONE, KeyCode::Menu),
+            ],
+        }
+    }
+}
+
+#[test]
+pub fn test_shortcut_matches() {
+    let redo = Shortcuts::default().redo;
+    let shifted = KeyEvent::new(
+        KeyCode::Char('Z'),
+        KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+    );
+    assert!(any_matches(&redo, &shifted));
+    assert!(!Shortcut::ctrl('z').matches(&shifted));
+    assert!(Shortcut::ctrl('z').matches(&KeyEvent::new(KeyCode::Char('z'), KeyModifiers::CONTROL)));
+}
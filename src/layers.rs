@@ -0,0 +1,19 @@
+//! Layers used by the built in containers, cells on a higher layer are drawn over lower ones
+
+use std::num::NonZeroU8;
+
+pub const BACKGROUND: NonZeroU8 = NonZeroU8::new(1).unwrap();
+pub const FRAME: NonZeroU8 = NonZeroU8::new(128).unwrap();
+/// floating windows start here, each window in front of another takes the next layer up
+pub const WINDOW: NonZeroU8 = NonZeroU8::new(140).unwrap();
+/// dialogs block and dim the frame, popups opened from them still go on top
+pub const MODAL: NonZeroU8 = NonZeroU8::new(180).unwrap();
+/// combo box lists and other popups float over the frame
+pub const POPUP: NonZeroU8 = NonZeroU8::new(200).unwrap();
+/// context menus, each open submenu takes the next layer up
+pub const MENU: NonZeroU8 = NonZeroU8::new(210).unwrap();
+/// hints next to the hovered widget, above popups they may come from
+pub const TOOLTIP: NonZeroU8 = NonZeroU8::new(230).unwrap();
+/// drag and drop previews follow the mouse above everything but debug output
+pub const DRAG: NonZeroU8 = NonZeroU8::new(250).unwrap();
+pub const DEBUG: NonZeroU8 = NonZeroU8::new(255).unwrap();
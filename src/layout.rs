@@ -0,0 +1,403 @@
+use crate::{
+    containers::split_pane::SplitDirection,
+    id::Id,
+    math_util::{Rect, VecI2},
+    ui::{Layout, Ui},
+};
+
+/// Where something smaller than its space goes within it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    #[default]
+    Start,
+    Center,
+    End,
+}
+
+impl Align {
+    /// Offset from the start of `space` for something `size` long
+    pub fn offset(&self, space: u16, size: u16) -> u16 {
+        let free = space.saturating_sub(size);
+        match self {
+            Align::Start => 0,
+            Align::Center => free / 2,
+            Align::End => free,
+        }
+    }
+}
+
+/// How items placed one after the other share the space along a layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Justify {
+    #[default]
+    Start,
+    Center,
+    End,
+    /// The first item at the start, the last at the end and the rest spread
+    /// evenly between them
+    SpaceBetween,
+}
+
+impl Justify {
+    /// The space before each item when items of `sizes` are placed one after
+    /// the other along `space`
+    pub fn gaps(&self, space: u16, sizes: &[u16]) -> Vec<u16> {
+        let used = sizes
+            .iter()
+            .fold(0u16, |used, &size| used.saturating_add(size));
+        let mut gaps = vec![0; sizes.len()];
+        let align = match self {
+            Justify::SpaceBetween if sizes.len() > 1 => {
+                let free = space.saturating_sub(used) as u32;
+                let parts = distribute(free, &vec![1; sizes.len() - 1]);
+                for (gap, part) in gaps[1..].iter_mut().zip(parts) {
+                    *gap = part as u16;
+                }
+                return gaps;
+            }
+            Justify::Start | Justify::SpaceBetween => Align::Start,
+            Justify::Center => Align::Center,
+            Justify::End => Align::End,
+        };
+        if let Some(first) = gaps.first_mut() {
+            *first = align.offset(space, used);
+        }
+        gaps
+    }
+}
+
+/// Where the items of a ui go, worked out from their sizes last frame
+#[derive(Debug, Clone)]
+pub(crate) struct Arrangement {
+    /// where the first item would go without any alignment
+    pub origin: VecI2,
+    /// max rect of the ui before any alignment
+    pub max: Rect,
+    /// space along the layout before every item
+    pub gaps: Vec<u16>,
+    /// offset across the layout of every item
+    pub offsets: Vec<u16>,
+    /// sizes of the items placed so far
+    pub sizes: Vec<VecI2>,
+    /// child uis take all the space across the layout
+    pub stretch: bool,
+}
+
+/// Aligns the items of a ui along its layout, left to right for a horizontal
+/// one, and across it. Items are placed using the size they had the frame
+/// before, a change in size is redrawn right away.
+pub struct Aligned {
+    id_source: Id,
+    main: Justify,
+    cross: Align,
+    stretch: bool,
+}
+
+impl Aligned {
+    pub fn new(id_source: impl std::hash::Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            main: Justify::Start,
+            cross: Align::Start,
+            stretch: false,
+        }
+    }
+
+    /// Along the layout
+    pub fn main(mut self, justify: Justify) -> Self {
+        self.main = justify;
+        self
+    }
+
+    /// Across the layout
+    pub fn cross(mut self, align: Align) -> Self {
+        self.cross = align;
+        self
+    }
+
+    /// Child uis, like the ones bordered boxes are drawn around, take all the
+    /// space across the layout instead of being aligned in it
+    pub fn stretch(mut self, stretch: bool) -> Self {
+        self.stretch = stretch;
+        self
+    }
+
+    pub fn show<R>(self, ui: &mut Ui, func: impl FnOnce(&mut Ui) -> R) -> R {
+        let ctx = ui.ctx().clone();
+        let id = ui.id().with(self.id_source);
+        let last: Vec<VecI2> = ctx.get_memory_or(id, Vec::new());
+        let max = ui.get_max();
+        let horizontal = ui.layout().is_primary_horizontal();
+        let (main_space, cross_space) = if horizontal {
+            (max.width, max.height)
+        } else {
+            (max.height, max.width)
+        };
+        let (along, across): (Vec<u16>, Vec<u16>) = last
+            .iter()
+            .map(|size| {
+                if horizontal {
+                    (size.x, size.y)
+                } else {
+                    (size.y, size.x)
+                }
+            })
+            .unzip();
+
+        let mut child = ui.child_ui(max, ui.layout());
+        child.arrange(Arrangement {
+            origin: child.get_cursor(),
+            max,
+            gaps: self.main.gaps(main_space, &along),
+            offsets: across
+                .iter()
+                .map(|&size| {
+                    if self.stretch {
+                        0
+                    } else {
+                        self.cross.offset(cross_space, size)
+                    }
+                })
+                .collect(),
+            sizes: Vec::new(),
+            stretch: self.stretch,
+        });
+        let inner = func(&mut child);
+        let sizes = child.take_arrangement().map_or(Vec::new(), |a| a.sizes);
+
+        let mut rect = child.get_current();
+        if self.stretch && horizontal {
+            rect.y = max.y;
+            rect.height = max.height;
+        } else if self.stretch {
+            rect.x = max.x;
+            rect.width = max.width;
+        }
+        let _ = ui.allocate_area(rect);
+
+        if sizes != last {
+            ctx.request_redraw();
+        }
+        ctx.insert_into_memory(id, sizes);
+        inner
+    }
+}
+
+/// How much of the space along a [`Split`] a chunk takes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Constraint {
+    /// Exactly this many cells
+    Length(u16),
+    /// A percentage of the space
+    Percentage(u16),
+    /// `numerator / denominator` of the space
+    Ratio(u32, u32),
+    /// At least this many cells, grows like `Fill(1)` when there is space left
+    Min(u16),
+    /// Grows like `Fill(1)` but never beyond this many cells
+    Max(u16),
+    /// Shares what is left after the other chunks with the other growing
+    /// chunks, by weight
+    Fill(u16),
+}
+
+impl Constraint {
+    /// The size before growing and how much it grows by
+    fn base(&self, space: u16) -> (u16, u32) {
+        let part = |num: u64, den: u64| (space as u64 * num / den.max(1)).min(space as u64) as u16;
+        match *self {
+            Constraint::Length(len) => (len, 0),
+            Constraint::Percentage(percent) => (part(percent as u64, 100), 0),
+            Constraint::Ratio(num, den) => (part(num as u64, den as u64), 0),
+            Constraint::Min(min) => (min, 1),
+            Constraint::Max(_) => (0, 1),
+            Constraint::Fill(weight) => (0, weight as u32),
+        }
+    }
+
+    fn cap(&self) -> u16 {
+        match *self {
+            Constraint::Max(max) => max,
+            _ => u16::MAX,
+        }
+    }
+}
+
+/// Splits the space of a ui into chunks along one direction, like a sidebar of
+/// 30 columns next to content that fills the rest. Splits can be nested by
+/// splitting a chunk again.
+pub struct Split {
+    direction: SplitDirection,
+    constraints: Vec<Constraint>,
+    spacing: u16,
+}
+
+impl Split {
+    pub fn new(direction: SplitDirection, constraints: impl Into<Vec<Constraint>>) -> Self {
+        Self {
+            direction,
+            constraints: constraints.into(),
+            spacing: 0,
+        }
+    }
+
+    /// Chunks side by side
+    pub fn horizontal(constraints: impl Into<Vec<Constraint>>) -> Self {
+        Self::new(SplitDirection::Horizontal, constraints)
+    }
+
+    /// Chunks one above the other
+    pub fn vertical(constraints: impl Into<Vec<Constraint>>) -> Self {
+        Self::new(SplitDirection::Vertical, constraints)
+    }
+
+    /// Empty cells between chunks
+    pub fn spacing(mut self, spacing: u16) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// The area of every chunk within `rect`
+    pub fn rects(&self, rect: Rect) -> Vec<Rect> {
+        let horizontal = self.direction == SplitDirection::Horizontal;
+        let length = if horizontal { rect.width } else { rect.height };
+        let sizes = solve(length, self.spacing, &self.constraints);
+
+        let mut start = if horizontal { rect.x } else { rect.y };
+        sizes
+            .into_iter()
+            .map(|size| {
+                let chunk = if horizontal {
+                    Rect::new_pos_size(VecI2::new(start, rect.y), VecI2::new(size, rect.height))
+                } else {
+                    Rect::new_pos_size(VecI2::new(rect.x, start), VecI2::new(rect.width, size))
+                };
+                start = start.saturating_add(size).saturating_add(self.spacing);
+                chunk
+            })
+            .collect()
+    }
+
+    /// Takes all the space left in `ui` and calls `func` with the index and a
+    /// ui for every chunk, clipped to that chunk
+    pub fn show<R>(self, ui: &mut Ui, mut func: impl FnMut(usize, &mut Ui) -> R) -> Vec<R> {
+        let ctx = ui.ctx().clone();
+        let rect = ui.get_max();
+        let _ = ui.allocate_area(rect);
+        self.rects(rect)
+            .into_iter()
+            .enumerate()
+            .map(|(i, chunk)| {
+                let mut child = ui.child_ui(chunk, Layout::TopLeftVertical);
+                ctx.scrolled(chunk, VecI2::new(0, 0), || func(i, &mut child))
+            })
+            .collect()
+    }
+}
+
+/// The size of every chunk along `length`. When the constraints ask for more
+/// than there is, fixed chunks shrink first and `Min` chunks last.
+pub fn solve(length: u16, spacing: u16, constraints: &[Constraint]) -> Vec<u16> {
+    let gaps = spacing.saturating_mul(constraints.len().saturating_sub(1) as u16);
+    let space = length.saturating_sub(gaps);
+    let (mut sizes, weights): (Vec<u16>, Vec<u32>) =
+        constraints.iter().map(|c| c.base(space)).unzip();
+
+    let total: u32 = sizes.iter().map(|&size| size as u32).sum();
+    if total > space as u32 {
+        let mut excess = total - space as u32;
+        for shrink_min in [false, true] {
+            let shrinkable: Vec<u32> = constraints
+                .iter()
+                .zip(&sizes)
+                .map(|(c, &size)| match c {
+                    Constraint::Min(_) if !shrink_min => 0,
+                    _ => size as u32,
+                })
+                .collect();
+            let available = shrinkable.iter().sum::<u32>();
+            let amount = excess.min(available);
+            for (size, cut) in sizes.iter_mut().zip(distribute(amount, &shrinkable)) {
+                *size -= cut as u16;
+            }
+            excess -= amount;
+        }
+        return sizes;
+    }
+
+    // growing chunks share what is left, chunks that hit their max drop out
+    // and what they could not take goes around again
+    let mut left = space as u32 - total;
+    let mut growing = weights;
+    while left > 0 && growing.iter().any(|&w| w > 0) {
+        let shares = distribute(left, &growing);
+        for (i, share) in shares.into_iter().enumerate() {
+            let room = constraints[i].cap().saturating_sub(sizes[i]) as u32;
+            let taken = share.min(room);
+            sizes[i] += taken as u16;
+            left -= taken;
+            if room <= share {
+                growing[i] = 0;
+            }
+        }
+    }
+    sizes
+}
+
+/// Splits `amount` by `weights`, rounding so the parts add up exactly
+fn distribute(amount: u32, weights: &[u32]) -> Vec<u32> {
+    let total = weights.iter().map(|&w| w as u64).sum::<u64>().max(1);
+    let mut acc = 0;
+    let mut given = 0;
+    weights
+        .iter()
+        .map(|&w| {
+            acc += w as u64;
+            let upto = (acc * amount as u64 / total) as u32;
+            let part = upto - given;
+            given = upto;
+            part
+        })
+        .collect()
+}
+
+#[test]
+pub fn test_solve_constraints() {
+    use Constraint::*;
+
+    // sidebar, content and footer
+    assert_eq!(solve(100, 0, &[Length(30), Fill(1)]), vec![30, 70]);
+    assert_eq!(solve(24, 0, &[Fill(1), Length(1)]), vec![23, 1]);
+    // three equal columns, rounding adds up
+    assert_eq!(solve(100, 0, &[Ratio(1, 3); 3]), vec![33, 33, 33]);
+    assert_eq!(solve(100, 0, &[Fill(1); 3]), vec![33, 33, 34]);
+    assert_eq!(solve(100, 2, &[Fill(1); 3]), vec![32, 32, 32]);
+    assert_eq!(
+        solve(100, 0, &[Percentage(25), Fill(1), Fill(3)]),
+        vec![25, 18, 57]
+    );
+    // max chunks stop growing and the rest goes to the others
+    assert_eq!(solve(100, 0, &[Max(10), Fill(1)]), vec![10, 90]);
+    assert_eq!(solve(10, 0, &[Max(10), Max(3)]), vec![7, 3]);
+    assert_eq!(solve(50, 0, &[Min(20), Fill(1)]), vec![35, 15]);
+    // not enough space, fixed chunks shrink before min ones
+    assert_eq!(solve(50, 0, &[Length(40), Min(20)]), vec![30, 20]);
+    assert_eq!(solve(10, 0, &[Length(40), Min(20)]), vec![0, 10]);
+    assert_eq!(solve(0, 1, &[Fill(1), Fill(1)]), vec![0, 0]);
+}
+
+#[test]
+pub fn test_justify_gaps() {
+    assert_eq!(Justify::Start.gaps(20, &[4, 6]), vec![0, 0]);
+    assert_eq!(Justify::Center.gaps(20, &[4, 6]), vec![5, 0]);
+    assert_eq!(Justify::End.gaps(20, &[4, 6]), vec![10, 0]);
+    assert_eq!(
+        Justify::SpaceBetween.gaps(20, &[2, 2, 2, 2]),
+        vec![0, 4, 4, 4]
+    );
+    assert_eq!(Justify::SpaceBetween.gaps(10, &[2, 2, 2]), vec![0, 2, 2]);
+    assert_eq!(Justify::SpaceBetween.gaps(11, &[2, 2, 2]), vec![0, 2, 3]);
+    // a single item stays at the start and too little space means no gaps
+    assert_eq!(Justify::SpaceBetween.gaps(20, &[4]), vec![0]);
+    assert_eq!(Justify::End.gaps(5, &[4, 6]), vec![0, 0]);
+}
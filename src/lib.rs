@@ -1,8 +1,8 @@
 use context::{Context, ContextInner, FinishedFrame};
 use crossterm::{
     event::{
-        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
-        KeyModifiers,
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers,
     },
     execute,
     style::Attribute,
@@ -22,10 +22,13 @@ use std::{
 };
 use style::Style;
 
+pub mod clipboard;
 pub mod containers;
 pub mod context;
 pub mod id;
 pub mod input;
+pub mod layers;
+pub mod layout;
 pub mod math_util;
 pub mod memory;
 pub mod response;
@@ -33,6 +36,7 @@ pub mod screen;
 pub mod style;
 pub mod symbols;
 pub mod ui;
+pub mod undo;
 pub mod widgets;
 
 pub trait App {
@@ -48,6 +52,7 @@ pub fn start_app(app: impl App) -> Result<(), io::Error> {
         // restore terminal
         disable_raw_mode().unwrap();
         execute!(stdout, LeaveAlternateScreen, DisableMouseCapture).unwrap();
+        execute!(stdout, DisableBracketedPaste).unwrap();
         execute!(stdout, EnableLineWrap).unwrap();
         execute!(stdout, crossterm::cursor::Show).unwrap();
 
@@ -60,6 +65,7 @@ pub fn start_app(app: impl App) -> Result<(), io::Error> {
     {
         enable_raw_mode()?;
         execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        execute!(stdout, EnableBracketedPaste)?;
         execute!(stdout, DisableLineWrap)?;
         execute!(stdout, crossterm::cursor::Hide)?;
         execute!(stdout, crossterm::event::EnableFocusChange)?;
@@ -80,6 +86,7 @@ pub fn start_app(app: impl App) -> Result<(), io::Error> {
         // restore terminal
         disable_raw_mode()?;
         execute!(stdout, LeaveAlternateScreen, DisableMouseCapture)?;
+        execute!(stdout, DisableBracketedPaste)?;
         execute!(stdout, EnableLineWrap)?;
         execute!(stdout, crossterm::cursor::Show)?;
         execute!(stdout, crossterm::event::DisableFocusChange)?;
@@ -126,6 +133,9 @@ fn run_app(mut stdout: Stdout, mut app: impl App) -> io::Result<()> {
             ctx.get_max_tick()
         };
         inner.request_redraw = false;
+        if let Some(redraw_at) = inner.redraw_at.take() {
+            tick_rate = tick_rate.min(redraw_at.saturating_duration_since(Instant::now()));
+        }
 
         last_frame = Instant::now();
 
@@ -180,6 +190,7 @@ fn output_to_terminal(
         mut last_frame,
         current_cursor,
         last_cursor,
+        clipboard,
     } = frame_report;
 
     if resized {
@@ -313,6 +324,9 @@ fn output_to_terminal(
     if let Some(cursor) = current_cursor {
         data.queue(crossterm::cursor::MoveTo(cursor.x, cursor.y))?;
     }
+    if let Some(clipboard) = clipboard {
+        data.extend_from_slice(clipboard.as_bytes());
+    }
 
     stdout.write_all(data)?;
     stdout.flush()?;
@@ -284,3 +284,55 @@ impl std::ops::Sub for VecI2 {
         self
     }
 }
+
+/// Numbers that sliders and drag values can edit, all math is done in `f64`
+pub trait Numeric: Copy + PartialOrd + std::fmt::Debug + 'static {
+    const INTEGRAL: bool;
+    const MIN: Self;
+    const MAX: Self;
+
+    fn to_f64(self) -> f64;
+
+    /// Integers round to the nearest value and saturate at their bounds
+    fn from_f64(value: f64) -> Self;
+}
+
+macro_rules! impl_numeric_integer {
+    ($($t:ty)*) => {$(
+        impl Numeric for $t {
+            const INTEGRAL: bool = true;
+            const MIN: Self = <$t>::MIN;
+            const MAX: Self = <$t>::MAX;
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn from_f64(value: f64) -> Self {
+                value.round() as $t
+            }
+        }
+    )*};
+}
+
+impl_numeric_integer!(i8 u8 i16 u16 i32 u32 i64 u64 i128 u128 isize usize);
+
+macro_rules! impl_numeric_float {
+    ($($t:ty)*) => {$(
+        impl Numeric for $t {
+            const INTEGRAL: bool = false;
+            const MIN: Self = <$t>::MIN;
+            const MAX: Self = <$t>::MAX;
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn from_f64(value: f64) -> Self {
+                value as $t
+            }
+        }
+    )*};
+}
+
+impl_numeric_float!(f32 f64);
@@ -1,34 +1,82 @@
+use std::{any::Any, rc::Rc, time::Duration};
+
 use crate::{
+    containers::{
+        menu,
+        tooltip::{Tooltip, TOOLTIP_DELAY},
+    },
+    context::Context,
     id::Id,
     input::mouse::MouseButtonState,
     math_util::{Rect, VecI2},
+    style::StyledText,
+    ui::Ui,
 };
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Response {
     pub hovered: bool,
+    /// hovered this frame but not last frame
+    pub hover_started: bool,
+    /// hovered last frame but not this frame
+    pub hover_ended: bool,
+    /// how long this widget has been continuously hovered, zero when not hovered
+    pub hover_duration: Duration,
     pub buttons: [MouseButtonState; 3],
+    /// the primary button started dragging this frame
+    pub drag_started: bool,
+    /// how far the mouse moved this frame while dragging
+    pub drag_delta: (i16, i16),
     pub id: Id,
     pub rect: Rect,
     pub mouse_pos: Option<VecI2>,
     pub focused: bool,
+    /// the value this widget edits was changed this frame
+    pub changed: bool,
+    /// positive values scroll right
+    pub delta_scroll_x: i16,
+    /// positive values scroll up
+    pub delta_scroll_y: i16,
+    /// Bracketed paste text, only delivered to the focused widget
+    pub paste: Option<String>,
 }
 
 impl Response {
     pub fn new(rect: Rect, id: Id, mouse: Option<VecI2>) -> Self {
         Self {
             hovered: mouse.map(|m| rect.contains(m)).unwrap_or(false),
+            hover_started: false,
+            hover_ended: false,
+            hover_duration: Duration::ZERO,
             buttons: Default::default(),
+            drag_started: false,
+            drag_delta: (0, 0),
             id,
             rect,
             mouse_pos: mouse,
             focused: false,
+            changed: false,
+            delta_scroll_x: 0,
+            delta_scroll_y: 0,
+            paste: None,
         }
     }
     pub fn hovered(&self) -> bool {
         self.hovered
     }
 
+    pub fn hover_started(&self) -> bool {
+        self.hover_started
+    }
+
+    pub fn hover_ended(&self) -> bool {
+        self.hover_ended
+    }
+
+    pub fn hover_duration(&self) -> Duration {
+        self.hover_duration
+    }
+
     pub fn released(&self) -> bool {
         matches!(
             self.buttons[0],
@@ -43,4 +91,102 @@ impl Response {
     pub fn pressed(&self) -> bool {
         self.buttons[0].is_down()
     }
+
+    pub fn focused(&self) -> bool {
+        self.focused
+    }
+
+    pub fn changed(&self) -> bool {
+        self.changed
+    }
+
+    pub fn mark_changed(&mut self) {
+        self.changed = true;
+    }
+
+    pub fn request_focus(&self, ctx: &Context) {
+        ctx.request_focus(self.id, self.rect);
+    }
+
+    pub fn surrender_focus(&self, ctx: &Context) {
+        ctx.surrender_focus(self.id);
+    }
+
+    pub fn dragged(&self) -> bool {
+        matches!(self.buttons[0], MouseButtonState::Drag { .. })
+    }
+
+    pub fn drag_started(&self) -> bool {
+        self.drag_started
+    }
+
+    pub fn drag_stopped(&self) -> bool {
+        matches!(self.buttons[0], MouseButtonState::DragReleased { .. })
+    }
+
+    pub fn drag_delta(&self) -> (i16, i16) {
+        self.drag_delta
+    }
+
+    pub fn scrolled(&self) -> bool {
+        self.delta_scroll_x != 0 || self.delta_scroll_y != 0
+    }
+
+    pub fn pasted(&self) -> Option<&str> {
+        self.paste.as_deref()
+    }
+
+    pub fn scroll_delta(&self) -> (i16, i16) {
+        (self.delta_scroll_x, self.delta_scroll_y)
+    }
+
+    /// Shows `text` in a tooltip, see [`Response::on_hover_ui`]
+    pub fn on_hover_text<'a>(self, ctx: &Context, text: impl Into<StyledText<'a>>) -> Self {
+        self.on_hover_ui(ctx, |ui| ui.label(text))
+    }
+
+    /// Shows `func` in a tooltip next to the widget once it has been hovered
+    /// or focused for [`TOOLTIP_DELAY`], hidden while it is pressed
+    pub fn on_hover_ui(self, ctx: &Context, func: impl FnOnce(&mut Ui)) -> Self {
+        if self.hovered && !self.pressed() {
+            if self.hover_duration >= TOOLTIP_DELAY {
+                Tooltip::new(self.id.with("tooltip"), self.rect).show(ctx, func);
+            } else {
+                ctx.request_redraw_after(TOOLTIP_DELAY - self.hover_duration);
+            }
+        }
+        self
+    }
+
+    /// Shows `func` in a menu where the widget is right clicked, or below it on
+    /// shift+f10 or the menu key while it is focused. See [`menu::MenuItem`]
+    pub fn context_menu(self, ctx: &Context, func: impl FnOnce(&mut Ui)) -> Self {
+        menu::context_menu(ctx, &self, func);
+        self
+    }
+
+    /// Starts a drag and drop from this widget once a drag starts on it
+    pub fn dnd_set_drag_payload<T: Any>(&self, ctx: &Context, payload: T) {
+        if self.drag_started() {
+            ctx.dnd_set_payload(self.id, payload);
+        }
+    }
+
+    /// The payload currently being dragged over this widget
+    pub fn dnd_hover_payload<T: Any>(&self, ctx: &Context) -> Option<Rc<T>> {
+        if self.mouse_pos.is_some_and(|pos| self.rect.contains(pos)) {
+            ctx.dnd_payload()
+        } else {
+            None
+        }
+    }
+
+    /// The payload dropped onto this widget this frame
+    pub fn dnd_release_payload<T: Any>(&self, ctx: &Context) -> Option<Rc<T>> {
+        if ctx.input().mouse.buttons[0].is_up() {
+            self.dnd_hover_payload(ctx)
+        } else {
+            None
+        }
+    }
 }
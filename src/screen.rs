@@ -1,4 +1,6 @@
-use std::num::NonZeroU8;
+use std::{collections::HashMap, num::NonZeroU8};
+
+use crossterm::style::Attribute;
 
 use super::{
     math_util::{Rect, VecI2},
@@ -244,17 +246,37 @@ impl Screen {
         }
     }
 
+    /// Dims every cell drawn on a layer below `layer`
+    pub fn dim_below(&mut self, layer: NonZeroU8) {
+        // cells share styles so every style gets one dimmed copy
+        let mut dimmed: HashMap<u16, u16> = HashMap::new();
+        for cell in &mut self.cells {
+            let CellData::Some(cell) = cell else {
+                continue;
+            };
+            if cell.layer >= layer {
+                continue;
+            }
+            cell.style_offet = *dimmed.entry(cell.style_offet).or_insert_with(|| {
+                let mut style = self.styles[cell.style_offet as usize];
+                style.attributes.set(Attribute::Dim);
+                self.styles.push(style);
+                self.styles.len() as u16 - 1
+            });
+        }
+    }
+
     pub fn clear(&mut self) {
         self.cells.fill(Default::default());
         self.styles.clear();
         self.text.clear();
     }
 
-    pub fn drain(&mut self) -> ScreenDrain {
+    pub fn drain(&mut self) -> ScreenDrain<'_> {
         ScreenDrain::new(self)
     }
 
-    pub fn iter(&mut self) -> ScreenIter {
+    pub fn iter(&mut self) -> ScreenIter<'_> {
         ScreenIter::new(self)
     }
 
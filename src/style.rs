@@ -349,10 +349,32 @@ pub struct DefaultStyle {
     pub button_active_clicked: Style,
     pub button_active_focused: Style,
 
+    pub text_edit: Style,
+    pub text_edit_focused: Style,
+    pub text_selection: Style,
+    pub placeholder: Style,
+
+    /// background of popups and other floating areas
+    pub popup: Style,
+    /// the highlighted entry of a list
+    pub list_highlight: Style,
+    /// background and border of tooltips
+    pub tooltip: Style,
+    /// background of every other row of a striped grid
+    pub stripe: Style,
+
     pub lines: &'static symbols::line::Set,
     pub blocks: &'static symbols::block::Set,
     pub bars: &'static symbols::bar::Set,
     pub pointers: &'static symbols::pointers::Set,
+    pub checks: &'static symbols::check::Set,
+    pub radios: &'static symbols::radio::Set,
+    pub toggles: &'static symbols::toggle::Set,
+    pub scrollbars: &'static symbols::scrollbar::Set,
+    /// drawn in place of each character of a password
+    pub password: &'static str,
+    /// the button that closes a window
+    pub close: &'static str,
 }
 impl DefaultStyle {
     pub fn new_unicode() -> Self {
@@ -397,10 +419,56 @@ impl DefaultStyle {
                 bg: Color::Grey,
                 attributes: Attributes::default(),
             },
+            text_edit: Style {
+                fg: Color::White,
+                bg: Color::Black,
+                attributes: Attributes::from(&[Attribute::Underlined][..]),
+            },
+            text_edit_focused: Style {
+                fg: Color::White,
+                bg: Color::DarkGrey,
+                attributes: Attributes::default(),
+            },
+            text_selection: Style {
+                fg: Color::Black,
+                bg: Color::White,
+                attributes: Attributes::default(),
+            },
+            placeholder: Style {
+                fg: Color::DarkGrey,
+                bg: Color::Black,
+                attributes: Attributes::from(&[Attribute::Underlined][..]),
+            },
+            popup: Style {
+                fg: Color::White,
+                bg: Color::Black,
+                attributes: Attributes::default(),
+            },
+            list_highlight: Style {
+                fg: Color::Black,
+                bg: Color::White,
+                attributes: Attributes::default(),
+            },
+            tooltip: Style {
+                fg: Color::White,
+                bg: Color::DarkGrey,
+                attributes: Attributes::default(),
+            },
+            stripe: Style {
+                fg: Color::White,
+                bg: Color::DarkGrey,
+                attributes: Attributes::default(),
+            },
             lines: &symbols::line::NORMAL,
             blocks: &symbols::block::NINE_LEVELS,
             bars: &symbols::bar::NINE_LEVELS,
             pointers: &symbols::pointers::TRIANGLE,
+            checks: &symbols::check::BALLOT,
+            radios: &symbols::radio::CIRCLE,
+            toggles: &symbols::toggle::SWITCH,
+            scrollbars: &symbols::scrollbar::SHADED,
+            password: symbols::DOT,
+            close: symbols::CROSS,
         }
     }
 
@@ -446,10 +514,56 @@ impl DefaultStyle {
                 bg: Color::Grey,
                 attributes: Attributes::default(),
             },
+            text_edit: Style {
+                fg: Color::White,
+                bg: Color::Black,
+                attributes: Attributes::from(&[Attribute::Underlined][..]),
+            },
+            text_edit_focused: Style {
+                fg: Color::White,
+                bg: Color::DarkGrey,
+                attributes: Attributes::default(),
+            },
+            text_selection: Style {
+                fg: Color::Black,
+                bg: Color::White,
+                attributes: Attributes::default(),
+            },
+            placeholder: Style {
+                fg: Color::DarkGrey,
+                bg: Color::Black,
+                attributes: Attributes::from(&[Attribute::Underlined][..]),
+            },
+            popup: Style {
+                fg: Color::White,
+                bg: Color::Black,
+                attributes: Attributes::default(),
+            },
+            list_highlight: Style {
+                fg: Color::Black,
+                bg: Color::White,
+                attributes: Attributes::default(),
+            },
+            tooltip: Style {
+                fg: Color::White,
+                bg: Color::DarkGrey,
+                attributes: Attributes::default(),
+            },
+            stripe: Style {
+                fg: Color::White,
+                bg: Color::DarkGrey,
+                attributes: Attributes::default(),
+            },
             lines: &symbols::line::ASCII,
             blocks: &symbols::block::THREE_LEVELS,
             bars: &symbols::bar::THREE_LEVELS,
             pointers: &symbols::pointers::ASCII,
+            checks: &symbols::check::ASCII,
+            radios: &symbols::radio::ASCII,
+            toggles: &symbols::toggle::ASCII,
+            scrollbars: &symbols::scrollbar::ASCII,
+            password: "*",
+            close: "x",
         }
     }
 }
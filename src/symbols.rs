@@ -21,6 +21,23 @@ pub mod block {
         pub empty: &'static str,
     }
 
+    impl Set {
+        /// The glyph for a cell filled `eighths` of the way, 0 is empty and 8 is full
+        pub fn eighths(&self, eighths: u32) -> &'static str {
+            match eighths {
+                0 => self.empty,
+                1 => self.one_eighth,
+                2 => self.one_quarter,
+                3 => self.three_eighths,
+                4 => self.half,
+                5 => self.five_eighths,
+                6 => self.three_quarters,
+                7 => self.seven_eighths,
+                _ => self.full,
+            }
+        }
+    }
+
     pub const THREE_LEVELS: Set = Set {
         full: FULL,
         seven_eighths: FULL,
@@ -69,6 +86,23 @@ pub mod bar {
         pub empty: &'static str,
     }
 
+    impl Set {
+        /// The glyph for a cell filled `eighths` of the way, 0 is empty and 8 is full
+        pub fn eighths(&self, eighths: u32) -> &'static str {
+            match eighths {
+                0 => self.empty,
+                1 => self.one_eighth,
+                2 => self.one_quarter,
+                3 => self.three_eighths,
+                4 => self.half,
+                5 => self.five_eighths,
+                6 => self.three_quarters,
+                7 => self.seven_eighths,
+                _ => self.full,
+            }
+        }
+    }
+
     pub const THREE_LEVELS: Set = Set {
         full: FULL,
         seven_eighths: FULL,
@@ -235,6 +269,7 @@ pub mod line {
 }
 
 pub const DOT: &str = "•";
+pub const CROSS: &str = "×";
 
 pub mod braille {
     pub const BLANK: u16 = 0x2800;
@@ -258,7 +293,7 @@ pub enum Marker {
 }
 
 pub mod pointers {
-    
+
     #[derive(Debug, Clone, PartialEq, Eq)]
     pub struct Set {
         pub up: &'static str,
@@ -331,3 +366,106 @@ pub mod pointers {
         left_up: ARROW_LEFT_UP,
     };
 }
+
+pub mod check {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Set {
+        pub checked: &'static str,
+        pub unchecked: &'static str,
+    }
+
+    pub const BALLOT_CHECKED: &str = "☑";
+    pub const BALLOT_UNCHECKED: &str = "☐";
+
+    pub const ASCII_CHECKED: &str = "[x]";
+    pub const ASCII_UNCHECKED: &str = "[ ]";
+
+    pub const BALLOT: Set = Set {
+        checked: BALLOT_CHECKED,
+        unchecked: BALLOT_UNCHECKED,
+    };
+
+    pub const ASCII: Set = Set {
+        checked: ASCII_CHECKED,
+        unchecked: ASCII_UNCHECKED,
+    };
+}
+
+pub mod radio {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Set {
+        pub selected: &'static str,
+        pub unselected: &'static str,
+    }
+
+    pub const CIRCLE_SELECTED: &str = "◉";
+    pub const CIRCLE_UNSELECTED: &str = "○";
+
+    pub const ASCII_SELECTED: &str = "(*)";
+    pub const ASCII_UNSELECTED: &str = "( )";
+
+    pub const CIRCLE: Set = Set {
+        selected: CIRCLE_SELECTED,
+        unselected: CIRCLE_UNSELECTED,
+    };
+
+    pub const ASCII: Set = Set {
+        selected: ASCII_SELECTED,
+        unselected: ASCII_UNSELECTED,
+    };
+}
+
+pub mod toggle {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Set {
+        pub on: &'static str,
+        pub off: &'static str,
+    }
+
+    pub const SWITCH_ON: &str = "━━●";
+    pub const SWITCH_OFF: &str = "●━━";
+
+    pub const ASCII_ON: &str = "[ o]";
+    pub const ASCII_OFF: &str = "[o ]";
+
+    pub const SWITCH: Set = Set {
+        on: SWITCH_ON,
+        off: SWITCH_OFF,
+    };
+
+    pub const ASCII: Set = Set {
+        on: ASCII_ON,
+        off: ASCII_OFF,
+    };
+}
+
+pub mod scrollbar {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Set {
+        pub vertical_track: &'static str,
+        pub vertical_thumb: &'static str,
+        pub horizontal_track: &'static str,
+        pub horizontal_thumb: &'static str,
+    }
+
+    pub const TRACK: &str = "░";
+    pub const THUMB: &str = "█";
+
+    pub const ASCII_VERTICAL_TRACK: &str = "|";
+    pub const ASCII_HORIZONTAL_TRACK: &str = "-";
+    pub const ASCII_THUMB: &str = "#";
+
+    pub const SHADED: Set = Set {
+        vertical_track: TRACK,
+        vertical_thumb: THUMB,
+        horizontal_track: TRACK,
+        horizontal_thumb: THUMB,
+    };
+
+    pub const ASCII: Set = Set {
+        vertical_track: ASCII_VERTICAL_TRACK,
+        vertical_thumb: ASCII_THUMB,
+        horizontal_track: ASCII_HORIZONTAL_TRACK,
+        horizontal_thumb: ASCII_THUMB,
+    };
+}
@@ -3,7 +3,23 @@ use std::num::NonZeroU8;
 use crossterm::style::Color;
 
 use crate::{
-    containers::{bordered::Bordered, drop_down::DropDown}, context::Context, id::Id, math_util::{Rect, VecI2}, response::Response, style::{Style, StyledText}, widgets::{button::Button, lable::Label, seperator::Separator}
+    containers::{
+        bordered::Bordered,
+        drag_source::{DragSource, DragSourceResponse},
+        drop_down::DropDown,
+        scroll_area::ScrollArea,
+    },
+    context::Context,
+    id::Id,
+    layout::{Align, Aligned, Arrangement, Justify},
+    math_util::{Numeric, Rect, VecI2},
+    response::Response,
+    style::{Style, StyledText},
+    widgets::{
+        button::Button, checkbox::Checkbox, combo_box::ComboBox, drag_value::DragValue,
+        lable::Label, radio_button::RadioButton, seperator::Separator, slider::Slider,
+        text_area::TextArea, text_edit::TextEdit, toggle::Toggle,
+    },
 };
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -85,6 +101,7 @@ pub struct Ui {
     cursor: VecI2,
     current: Rect,
     layer: NonZeroU8,
+    arrangement: Option<Arrangement>,
 }
 
 impl Ui {
@@ -105,9 +122,14 @@ impl Ui {
             cursor,
             current: Rect::new_pos_size(cursor, VecI2::new(0, 0)),
             layer,
+            arrangement: None,
         }
     }
 
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
     pub fn get_clip(&self) -> Rect {
         self.clip
     }
@@ -149,14 +171,40 @@ impl Ui {
         self.context.interact(self.clip, self.layer, id, area)
     }
 
+    /// Interacts without becoming a focus stop, see [`Context::interact_mouse`]
+    pub fn interact_mouse(&mut self, id: Id, area: Rect) -> Response {
+        self.context.interact_mouse(self.clip, self.layer, id, area)
+    }
+
+    pub fn interact_scroll(&mut self, id: Id, area: Rect) -> Response {
+        self.context
+            .interact_scroll(self.clip, self.layer, id, area)
+    }
+
+    /// Scroll interaction without becoming a focus stop, see [`Context::interact_scroll_mouse`]
+    pub fn interact_scroll_mouse(&mut self, id: Id, area: Rect) -> Response {
+        self.context
+            .interact_scroll_mouse(self.clip, self.layer, id, area)
+    }
+
     pub fn child_ui(&mut self, max_rect: Rect, layout: Layout) -> Self {
-        Self::new(
+        let mut child = Self::new(
             self.ctx().clone(),
             layout,
             Id::new(self.next_id_source()).with("child"),
             max_rect,
             self.layer,
-        )
+        );
+        // a stretched child starts out as big as it can be across the layout
+        if self.arrangement.as_ref().is_some_and(|a| a.stretch) {
+            let across = if self.layout.is_primary_horizontal() {
+                Rect::new_pos_size(max_rect.top_left(), VecI2::new(0, max_rect.height))
+            } else {
+                Rect::new_pos_size(max_rect.top_left(), VecI2::new(max_rect.width, 0))
+            };
+            child.current.expand_to_include(&across);
+        }
+        child
     }
 
     pub fn draw(&mut self, text: &str, style: Style, start: VecI2, clip: Rect) {
@@ -282,7 +330,12 @@ impl Ui {
         let y = old_cursor.y.min(new_cursor.y);
         let width = old_cursor.x.abs_diff(new_cursor.x);
         let height = old_cursor.y.abs_diff(new_cursor.y);
-        Rect::new_pos_size(VecI2::new(x, y), VecI2::new(width, height))
+        let rect = Rect::new_pos_size(VecI2::new(x, y), VecI2::new(width, height));
+        if let Some(arrangement) = &mut self.arrangement {
+            arrangement.sizes.push(rect.size());
+            self.place_next_item();
+        }
+        rect
     }
 
     pub fn add_horizontal_space(&mut self, space: u16) {
@@ -395,6 +448,63 @@ impl Ui {
     }
 }
 
+// alignment
+impl Ui {
+    /// Places every item allocated from now on as `arrangement` says
+    pub(crate) fn arrange(&mut self, arrangement: Arrangement) {
+        self.arrangement = Some(arrangement);
+        self.place_next_item();
+    }
+
+    pub(crate) fn take_arrangement(&mut self) -> Option<Arrangement> {
+        self.arrangement.take()
+    }
+
+    /// Moves the cursor along by the gap before the next item and across by its
+    /// offset, with the max rect so containers start there too
+    fn place_next_item(&mut self) {
+        let Some(arrangement) = &self.arrangement else {
+            return;
+        };
+        let index = arrangement.sizes.len();
+        let gap = arrangement.gaps.get(index).copied().unwrap_or(0);
+        let offset = arrangement.offsets.get(index).copied().unwrap_or(0);
+        let (origin, max) = (arrangement.origin, arrangement.max);
+
+        // back in line first so the gap does not move the max rect across
+        if self.layout.is_primary_horizontal() {
+            self.cursor.y = origin.y;
+            self.max_rect.y = max.y;
+            self.max_rect.height = max.height;
+        } else {
+            self.cursor.x = origin.x;
+            self.max_rect.x = max.x;
+            self.max_rect.width = max.width;
+        }
+        self.add_space_primary_direction(gap);
+        match self.layout {
+            Layout::TopLeftHorizontal | Layout::TopRightHorizontal => {
+                self.cursor.y = origin.y.saturating_add(offset);
+                self.max_rect.y = self.cursor.y;
+                self.max_rect.height = max.height.saturating_sub(offset);
+            }
+            Layout::BottomLeftHorizontal | Layout::BottomRightHorizontal => {
+                self.cursor.y = origin.y.saturating_sub(offset);
+                self.max_rect.height = max.height.saturating_sub(offset);
+            }
+            Layout::TopLeftVertical | Layout::BottomLeftVertical => {
+                self.cursor.x = origin.x.saturating_add(offset);
+                self.max_rect.x = self.cursor.x;
+                self.max_rect.width = max.width.saturating_sub(offset);
+            }
+            Layout::TopRightVertical | Layout::BottomRightVertical => {
+                self.cursor.x = origin.x.saturating_sub(offset);
+                self.max_rect.width = max.width.saturating_sub(offset);
+            }
+        }
+    }
+}
+
 // widget helpers
 impl Ui {
     pub fn label<'a>(&mut self, text: impl Into<StyledText<'a>>) {
@@ -405,9 +515,65 @@ impl Ui {
         Button::new(text).show(self)
     }
 
+    pub fn checkbox<'a>(
+        &mut self,
+        checked: &mut bool,
+        text: impl Into<StyledText<'a>>,
+    ) -> Response {
+        Checkbox::new(checked, text).show(self)
+    }
+
+    pub fn radio<'a>(&mut self, selected: bool, text: impl Into<StyledText<'a>>) -> Response {
+        RadioButton::new(selected, text).show(self)
+    }
+
+    /// A radio button that sets `current` to `value` when picked
+    pub fn radio_value<'a, T: PartialEq>(
+        &mut self,
+        current: &mut T,
+        value: T,
+        text: impl Into<StyledText<'a>>,
+    ) -> Response {
+        let mut response = self.radio(*current == value, text);
+        if response.clicked() && *current != value {
+            *current = value;
+            response.mark_changed();
+        }
+        response
+    }
+
+    pub fn slider<N: Numeric>(
+        &mut self,
+        value: &mut N,
+        range: std::ops::RangeInclusive<N>,
+    ) -> Response {
+        Slider::new(value, range).show(self)
+    }
+
+    pub fn drag_value<N: Numeric>(&mut self, value: &mut N) -> Response {
+        DragValue::new(value).show(self)
+    }
+
+    /// Picks one of `options` from a popup list, see [`ComboBox`] for more settings
+    pub fn combo_box<S: AsRef<str>>(&mut self, selected: &mut usize, options: &[S]) -> Response {
+        ComboBox::new(self.next_id_source()).show_index(self, selected, options)
+    }
+
+    pub fn toggle(&mut self, on: &mut bool) -> Response {
+        Toggle::new(on).show(self)
+    }
+
     pub fn seperator(&mut self) {
         Separator::new().show(self);
     }
+
+    pub fn text_edit_singleline(&mut self, text: &mut String) -> Response {
+        TextEdit::new(text).show(self)
+    }
+
+    pub fn text_edit_multiline(&mut self, text: &mut String) -> Response {
+        TextArea::new(text).show(self)
+    }
 }
 
 // container/layout helpers
@@ -435,12 +601,46 @@ impl Ui {
 
     pub fn drop_down<'a>(&mut self, title: impl Into<StyledText<'a>>, func: impl FnOnce(&mut Ui)) {
         DropDown::new(title).show(self, |ui, _| func(ui));
-    }   
+    }
+
+    /// Scrolls vertically when `func` shows more than fits, see [`ScrollArea`] for more settings
+    pub fn scroll_area<R>(&mut self, func: impl FnOnce(&mut Ui) -> R) -> R {
+        ScrollArea::vertical(self.next_id_source())
+            .show(self, func)
+            .inner
+    }
+
+    /// Aligns the items of `func` along and across the layout, see [`Aligned`]
+    pub fn aligned<R>(
+        &mut self,
+        main: Justify,
+        cross: Align,
+        func: impl FnOnce(&mut Ui) -> R,
+    ) -> R {
+        Aligned::new(self.next_id_source())
+            .main(main)
+            .cross(cross)
+            .show(self, func)
+    }
+
+    /// Centers the items of `func` both ways
+    pub fn centered<R>(&mut self, func: impl FnOnce(&mut Ui) -> R) -> R {
+        self.aligned(Justify::Center, Align::Center, func)
+    }
 
     pub fn bordered<R>(&mut self, func: impl FnOnce(&mut Ui) -> R) -> R {
         Bordered::new().show(self, func)
     }
 
+    pub fn dnd_drag_source<T: std::any::Any, R>(
+        &mut self,
+        id_source: impl std::hash::Hash,
+        payload: T,
+        func: impl FnOnce(&mut Ui) -> R,
+    ) -> DragSourceResponse<R> {
+        DragSource::new(id_source).show(self, payload, func)
+    }
+
     pub fn tabbed_area<'a, F: FnOnce(usize, &mut Self) -> R, R, const L: usize>(
         &mut self,
         id: Id,
@@ -0,0 +1,204 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use crate::context::Context;
+
+/// Kinds of edits that get merged into a single undo step when they follow each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UndoGroup {
+    Typing,
+    Deleting,
+    Dragging,
+    /// changes noticed by [`Undoer::feed`]
+    Changed,
+}
+
+/// An undo/redo history of snapshots of `T`.
+///
+/// Widgets keep one in memory per id, apps can own one for any state they want to
+/// snapshot and restore.
+#[derive(Debug, Clone)]
+pub struct Undoer<T> {
+    undos: VecDeque<T>,
+    redos: Vec<T>,
+    max_undos: usize,
+    /// edits of the same group further apart than this are not merged
+    coalesce_time: Duration,
+    last_edit: Option<(UndoGroup, Instant)>,
+    /// the last state seen by `feed`
+    fed: Option<T>,
+}
+
+impl<T> Default for Undoer<T> {
+    fn default() -> Self {
+        Self {
+            undos: VecDeque::new(),
+            redos: Vec::new(),
+            max_undos: 100,
+            coalesce_time: Duration::from_secs(1),
+            last_edit: None,
+            fed: None,
+        }
+    }
+}
+
+impl<T: Clone> Undoer<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_undos(mut self, max_undos: usize) -> Self {
+        self.max_undos = max_undos.max(1);
+        self
+    }
+
+    pub fn coalesce_time(mut self, coalesce_time: Duration) -> Self {
+        self.coalesce_time = coalesce_time;
+        self
+    }
+
+    pub fn has_undo(&self) -> bool {
+        !self.undos.is_empty()
+    }
+
+    pub fn has_redo(&self) -> bool {
+        !self.redos.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undos.clear();
+        self.redos.clear();
+        self.last_edit = None;
+    }
+
+    /// The next edit starts a new undo step even if it is in the same group
+    pub fn end_group(&mut self) {
+        self.last_edit = None;
+    }
+
+    /// Whether an edit in `group` made now would be merged into the previous one,
+    /// so there is no need to take a snapshot for it
+    pub fn merges(&self, group: Option<UndoGroup>) -> bool {
+        match (group, self.last_edit) {
+            (Some(group), Some((last, at))) => {
+                group == last && Instant::now().saturating_duration_since(at) <= self.coalesce_time
+            }
+            _ => false,
+        }
+    }
+
+    /// Record `before`, the state just before an edit. When `group` matches the
+    /// previous edit the two are merged and `before` is dropped.
+    pub fn record(&mut self, before: T, group: Option<UndoGroup>) {
+        let before = (!self.merges(group)).then_some(before);
+        self.record_snapshot(before, group);
+    }
+
+    /// Record an edit with the snapshot taken when [`Undoer::merges`] was false,
+    /// `None` merges the edit into the previous undo step
+    pub fn record_snapshot(&mut self, before: Option<T>, group: Option<UndoGroup>) {
+        self.redos.clear();
+        self.last_edit = group.map(|group| (group, Instant::now()));
+
+        if let Some(before) = before {
+            if self.undos.len() >= self.max_undos {
+                self.undos.pop_front();
+            }
+            self.undos.push_back(before);
+        }
+    }
+
+    /// Returns the state to restore, `current` becomes redoable
+    pub fn undo(&mut self, current: &T) -> Option<T> {
+        let state = self.undos.pop_back()?;
+        self.redos.push(current.clone());
+        self.last_edit = None;
+        self.fed = Some(state.clone());
+        Some(state)
+    }
+
+    /// Returns the state to restore, `current` becomes undoable
+    pub fn redo(&mut self, current: &T) -> Option<T> {
+        let state = self.redos.pop()?;
+        self.undos.push_back(current.clone());
+        self.last_edit = None;
+        self.fed = Some(state.clone());
+        Some(state)
+    }
+}
+
+impl<T: Clone> Undoer<T> {
+    /// Applies the undo/redo shortcuts pressed this frame to `current`, returns
+    /// whether it changed. The keys are consumed so nothing else acts on them.
+    pub fn apply_shortcuts(&mut self, ctx: &Context, current: &mut T) -> bool {
+        let shortcuts = ctx.shortcuts().borrow().clone();
+        let mut changed = false;
+        while ctx.consume_shortcut(&shortcuts.undo) {
+            if let Some(state) = self.undo(current) {
+                *current = state;
+                changed = true;
+            }
+        }
+        while ctx.consume_shortcut(&shortcuts.redo) {
+            if let Some(state) = self.redo(current) {
+                *current = state;
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+impl<T: Clone + PartialEq> Undoer<T> {
+    /// Call every frame with the current state, changes are recorded on their own
+    /// and bursts of changes close together become one undo step
+    pub fn feed(&mut self, current: &T) {
+        match &self.fed {
+            Some(fed) if fed == current => {}
+            Some(_) => {
+                let before = self.fed.replace(current.clone()).unwrap();
+                self.record(before, Some(UndoGroup::Changed));
+            }
+            None => self.fed = Some(current.clone()),
+        }
+    }
+}
+
+#[test]
+pub fn test_undo_coalescing() {
+    let mut undoer = Undoer::new();
+    let mut text = String::new();
+    for c in "abc".chars() {
+        undoer.record(text.clone(), Some(UndoGroup::Typing));
+        text.push(c);
+    }
+    undoer.record(text.clone(), Some(UndoGroup::Deleting));
+    text.pop();
+
+    text = undoer.undo(&text).unwrap();
+    assert_eq!(text, "abc");
+    text = undoer.undo(&text).unwrap();
+    assert_eq!(text, "");
+    assert!(undoer.undo(&text).is_none());
+
+    text = undoer.redo(&text).unwrap();
+    assert_eq!(text, "abc");
+    text = undoer.redo(&text).unwrap();
+    assert_eq!(text, "ab");
+    assert!(!undoer.has_redo());
+
+    let mut undoer = Undoer::new();
+    for value in [1, 1, 2, 3] {
+        undoer.feed(&value);
+    }
+    assert_eq!(undoer.undo(&3), Some(1));
+
+    let mut undoer = Undoer::new();
+    undoer.record(String::new(), Some(UndoGroup::Typing));
+    assert!(undoer.merges(Some(UndoGroup::Typing)));
+    assert!(!undoer.merges(Some(UndoGroup::Deleting)));
+    undoer.record_snapshot(None, Some(UndoGroup::Typing));
+    assert_eq!(undoer.undo(&"ab".to_owned()), Some(String::new()));
+}
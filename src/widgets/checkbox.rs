@@ -0,0 +1,117 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::{
+    context::Cursor,
+    id::Id,
+    input::mouse::MouseButtonState,
+    math_util::{Rect, VecI2},
+    response::Response,
+    style::StyledText,
+    ui::Ui,
+};
+
+/// Draws `glyph` followed by `text` as one focusable widget, space on the focused
+/// widget clicks it the same way enter does
+pub(crate) fn show_glyph_label(
+    ui: &mut Ui,
+    glyph: &str,
+    active: bool,
+    text: &StyledText,
+) -> Response {
+    let id = Id::new(ui.next_id_source());
+    let default_style = *ui.ctx().style().borrow();
+
+    let glyph_width = unicode_width::UnicodeWidthStr::width(glyph) as u16;
+    let label_offset = if text.text.is_empty() {
+        glyph_width
+    } else {
+        glyph_width + 1
+    };
+
+    let mut gallery = ui.create_gallery(text);
+    let bound = gallery.bound;
+    let desired = Rect::new_pos_size(
+        bound.top_left(),
+        VecI2::new(bound.width + label_offset, bound.height.max(1)),
+    );
+    let area = ui.allocate_area(desired);
+    for (rect, _) in &mut gallery.items {
+        rect.x = rect.x + area.x + label_offset - bound.x;
+        rect.y = rect.y + area.y - bound.y;
+    }
+    gallery.bound = area;
+
+    let mut response = ui.interact(id, area);
+    let space = response.focused()
+        && ui.ctx().input().keyboard.events.iter().any(|key| {
+            key.code == KeyCode::Char(' ')
+                && !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        });
+    if space {
+        ui.ctx().request_redraw();
+        response.buttons[0] = MouseButtonState::Down(area.top_left());
+    }
+
+    let style = match (active, response.pressed(), response.hovered()) {
+        (false, true, _) => default_style.button_clicked,
+        (false, _, true) => default_style.button_hovered,
+        (false, ..) if response.focused() => default_style.button_focused,
+        (false, ..) => default_style.button,
+        (true, true, _) => default_style.button_active_clicked,
+        (true, _, true) => default_style.button_active_hovered,
+        (true, ..) if response.focused() => default_style.button_active_focused,
+        (true, ..) => default_style.button_active,
+    };
+    let glyph_rect = Rect::new_pos_size(area.top_left(), VecI2::new(glyph_width, 1));
+    ui.draw(glyph, style, glyph_rect.top_left(), glyph_rect);
+
+    if response.hovered() {
+        for item in &mut gallery.items {
+            item.1.underline(true);
+        }
+    }
+    ui.draw_gallery(gallery);
+
+    // the terminal cursor marks which one has keyboard focus
+    if response.focused() {
+        ui.ctx().set_cursor(Cursor {
+            x: area.x,
+            y: area.y,
+        });
+    }
+
+    response
+}
+
+/// A check box bound to a `bool`
+pub struct Checkbox<'a, 'b> {
+    checked: &'a mut bool,
+    text: StyledText<'b>,
+}
+
+impl<'a, 'b> Checkbox<'a, 'b> {
+    pub fn new(checked: &'a mut bool, text: impl Into<StyledText<'b>>) -> Self {
+        Self {
+            checked,
+            text: text.into(),
+        }
+    }
+
+    pub fn show(self, ui: &mut Ui) -> Response {
+        let checks = ui.ctx().style().borrow().checks;
+        let glyph = if *self.checked {
+            checks.checked
+        } else {
+            checks.unchecked
+        };
+
+        let mut response = show_glyph_label(ui, glyph, false, &self.text);
+        if response.clicked() {
+            *self.checked ^= true;
+            response.mark_changed();
+        }
+        response
+    }
+}
@@ -0,0 +1,315 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::{
+    containers::popup::Popup,
+    context::{Cursor, FocusLock},
+    id::Id,
+    input::mouse::MouseButtonState,
+    math_util::{Rect, VecI2},
+    response::Response,
+    ui::Ui,
+};
+
+use super::text_edit::{draw_line, str_width};
+
+/// Typing again after this long starts a new search
+const SEARCH_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ComboBoxState {
+    pub open: bool,
+    pub highlighted: usize,
+    /// first option shown in the list
+    pub scroll: usize,
+    pub search: String,
+    pub last_search: Option<Instant>,
+}
+
+/// Index of the first option starting with `search`, falling back to the first containing it
+fn find_option<S: AsRef<str>>(options: &[S], search: &str) -> Option<usize> {
+    let search = search.to_lowercase();
+    let lowered = options.iter().map(|option| option.as_ref().to_lowercase());
+    lowered
+        .clone()
+        .position(|option| option.starts_with(&search))
+        .or_else(|| lowered.clone().position(|option| option.contains(&search)))
+}
+
+/// Shows the selected option and opens a list of all options in a popup over the
+/// rest of the ui. Open with a click, enter, space or alt+down; pick with the mouse,
+/// the arrow keys and enter, or by typing the start of an option.
+pub struct ComboBox {
+    id_source: Id,
+    width: Option<u16>,
+    max_rows: u16,
+}
+
+impl ComboBox {
+    pub fn new(id_source: impl std::hash::Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            width: None,
+            max_rows: 8,
+        }
+    }
+
+    /// Width of the closed box, defaults to fit the longest option
+    pub fn width(mut self, width: u16) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Longer lists scroll
+    pub fn max_rows(mut self, max_rows: u16) -> Self {
+        self.max_rows = max_rows.max(1);
+        self
+    }
+
+    /// Picks `current` out of `options`, `changed()` is set when another one was picked
+    pub fn show_values<T: PartialEq + Clone>(
+        self,
+        ui: &mut Ui,
+        current: &mut T,
+        options: &[(T, &str)],
+    ) -> Response {
+        let mut index = options
+            .iter()
+            .position(|(value, _)| value == current)
+            .unwrap_or(usize::MAX);
+        let labels: Vec<&str> = options.iter().map(|(_, label)| *label).collect();
+        let response = self.show_index(ui, &mut index, &labels);
+        if response.changed() {
+            *current = options[index].0.clone();
+        }
+        response
+    }
+
+    /// Picks an index into `options`, an out of range `selected` shows as empty
+    pub fn show_index<S: AsRef<str>>(
+        self,
+        ui: &mut Ui,
+        selected: &mut usize,
+        options: &[S],
+    ) -> Response {
+        let ctx = ui.ctx().clone();
+        let default_style = *ctx.style().borrow();
+        let id = ui.id().with(self.id_source);
+        let mut state = ctx.get_memory_or(id, ComboBoxState::default());
+
+        let arrow = default_style.pointers.down;
+        let arrow_width = str_width(arrow, None);
+        let width = self.width.unwrap_or_else(|| {
+            options
+                .iter()
+                .map(|option| str_width(option.as_ref(), None))
+                .max()
+                .unwrap_or(0)
+                + arrow_width
+                + 1
+        });
+        let area = ui.allocate_size(VecI2::new(width, 1));
+        let mut response = ui.interact(id, area);
+
+        let keyboard = &ctx.input().keyboard;
+        let mouse = ctx.input().mouse;
+        let rows = (self.max_rows as usize).min(options.len());
+        let mut pick = None;
+        // options can go away between frames while the list is open
+        if options.is_empty() {
+            state.open = false;
+        }
+        state.highlighted = state.highlighted.min(options.len().saturating_sub(1));
+        let was_open = state.open;
+
+        if !state.open {
+            let open_key = response.focused()
+                && keyboard.events.iter().any(|key| {
+                    key.code == KeyCode::Char(' ')
+                        || (key.code == KeyCode::Down && key.modifiers == KeyModifiers::ALT)
+                });
+            // clicked() also covers enter on the focused box
+            if (response.clicked() || open_key) && !options.is_empty() {
+                response.request_focus(&ctx);
+                state.open = true;
+                state.highlighted = (*selected).min(options.len() - 1);
+                state.search.clear();
+            }
+        } else {
+            ctx.lock_focus(FocusLock {
+                horizontal: true,
+                vertical: true,
+                tab: true,
+            });
+
+            for key in &keyboard.events {
+                let last = options.len().saturating_sub(1);
+                match key.code {
+                    KeyCode::Up => state.highlighted = state.highlighted.saturating_sub(1),
+                    KeyCode::Down => state.highlighted = (state.highlighted + 1).min(last),
+                    KeyCode::PageUp => state.highlighted = state.highlighted.saturating_sub(rows),
+                    KeyCode::PageDown => state.highlighted = (state.highlighted + rows).min(last),
+                    KeyCode::Home => state.highlighted = 0,
+                    KeyCode::End => state.highlighted = last,
+                    KeyCode::Enter => pick = Some(state.highlighted),
+                    KeyCode::Esc | KeyCode::Tab | KeyCode::BackTab => state.open = false,
+                    KeyCode::Backspace => {
+                        state.search.pop();
+                    }
+                    KeyCode::Char(c)
+                        if !key
+                            .modifiers
+                            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+                    {
+                        let now = Instant::now();
+                        if state
+                            .last_search
+                            .is_some_and(|last| now.duration_since(last) > SEARCH_TIMEOUT)
+                        {
+                            state.search.clear();
+                        }
+                        state.last_search = Some(now);
+                        state.search.push(c);
+                        if let Some(found) = find_option(options, &state.search) {
+                            state.highlighted = found;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if state.open {
+            // keep the highlighted option in view
+            if state.highlighted < state.scroll {
+                state.scroll = state.highlighted;
+            } else if state.highlighted >= state.scroll + rows {
+                state.scroll = state.highlighted + 1 - rows;
+            }
+
+            let popup = Popup::new(id.with("popup"), area)
+                .fixed_size(VecI2::new(width, rows as u16))
+                .show(&ctx, |ui| {
+                    let inner = ui.get_max();
+                    // the popup is in screen coordinates even inside a scroll area
+                    let mouse = ui.ctx().input().mouse;
+                    let hovered_row = mouse
+                        .position
+                        .filter(|pos| inner.contains(*pos))
+                        .map(|pos| state.scroll + (pos.y - inner.y) as usize);
+
+                    if hovered_row.is_some() && mouse.delta_scroll_y != 0 {
+                        let max_scroll = options.len() - rows;
+                        state.scroll = state
+                            .scroll
+                            .saturating_add_signed(-mouse.delta_scroll_y as isize)
+                            .min(max_scroll);
+                    }
+                    if let Some(row) = hovered_row.filter(|row| *row < options.len()) {
+                        if mouse.delta() != (0, 0) {
+                            state.highlighted = row;
+                        }
+                        // the click that opened a popup clamped over the box is not a pick
+                        if was_open && matches!(mouse.buttons[0], MouseButtonState::Down(_)) {
+                            pick = Some(row);
+                        }
+                    }
+
+                    for (row, option) in options.iter().enumerate().skip(state.scroll).take(rows) {
+                        let style = if row == state.highlighted {
+                            default_style.list_highlight
+                        } else {
+                            default_style.popup
+                        };
+                        let marker = if row == *selected {
+                            default_style.pointers.right
+                        } else {
+                            " "
+                        };
+                        let y = inner.y + (row - state.scroll) as u16;
+                        let line = format!("{marker}{}", option.as_ref());
+                        let rect =
+                            Rect::new_pos_size(VecI2::new(inner.x, y), VecI2::new(inner.width, 1));
+                        draw_line(ui, &line, 0, rect, 0, 0..0, None, 0, style, style);
+                    }
+
+                    // show that there is more above or below
+                    let right = inner.x + inner.width;
+                    if state.scroll > 0 {
+                        let pos = VecI2::new(right, inner.y);
+                        ui.draw(
+                            default_style.pointers.up,
+                            default_style.popup,
+                            pos,
+                            Rect::new_pos_size(pos, VecI2::new(1, 1)),
+                        );
+                    }
+                    if state.scroll + rows < options.len() {
+                        let pos = VecI2::new(right, inner.y + rows as u16 - 1);
+                        ui.draw(
+                            default_style.pointers.down,
+                            default_style.popup,
+                            pos,
+                            Rect::new_pos_size(pos, VecI2::new(1, 1)),
+                        );
+                    }
+                });
+
+            let clicked_box =
+                matches!(mouse.buttons[0], MouseButtonState::Down(pos) if area.contains(pos));
+            if was_open && pick.is_none() && (popup.clicked_outside || !response.focused()) {
+                state.open = false;
+                // clicking the box again only closes it
+                if clicked_box {
+                    response.buttons[0] = MouseButtonState::UnPressed;
+                }
+            }
+        }
+
+        if let Some(pick) = pick {
+            state.open = false;
+            if *selected != pick {
+                *selected = pick;
+                response.mark_changed();
+            }
+        }
+
+        let style = if response.pressed() {
+            default_style.button_clicked
+        } else if response.hovered() {
+            default_style.button_hovered
+        } else if response.focused() {
+            default_style.button_focused
+        } else {
+            default_style.button
+        };
+        let label = options
+            .get(*selected)
+            .map(|option| option.as_ref())
+            .unwrap_or("");
+        let label_width = str_width(label, None);
+        let padding = width.saturating_sub(label_width + arrow_width) as usize;
+        let text = format!("{label}{}{arrow}", " ".repeat(padding));
+        draw_line(ui, &text, 0, area, 0, 0..0, None, 0, style, style);
+        if response.focused() && !state.open {
+            ctx.set_cursor(Cursor {
+                x: area.x,
+                y: area.y,
+            });
+        }
+
+        ctx.insert_into_memory(id, state);
+        response
+    }
+}
+
+#[test]
+pub fn test_find_option() {
+    let options = ["Apple", "Banana", "Cherry", "Pineapple"];
+    assert_eq!(find_option(&options, "b"), Some(1));
+    assert_eq!(find_option(&options, "CH"), Some(2));
+    assert_eq!(find_option(&options, "pine"), Some(3));
+    assert_eq!(find_option(&options, "nana"), Some(1));
+    assert_eq!(find_option(&options, "kiwi"), None);
+}
@@ -0,0 +1,299 @@
+use std::ops::RangeInclusive;
+
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::{
+    context::{Cursor, FocusLock},
+    id::Id,
+    input::mouse::MouseButtonState,
+    math_util::{Numeric, VecI2},
+    response::Response,
+    style::Style,
+    ui::Ui,
+    undo::UndoGroup,
+};
+
+use super::{
+    slider::{format_value, undo_value, Formatter},
+    text_edit::{apply_key, draw_line, insert_text, str_width, TextCursor},
+};
+
+pub type Parser<'a> = Box<dyn Fn(&str) -> Option<f64> + 'a>;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DragValueState {
+    /// the text being typed in and its caret, `None` while not editing
+    pub editing: Option<(String, TextCursor)>,
+}
+
+/// A number that is edited by dragging left/right, the up/down keys or by
+/// clicking it (or pressing enter) and typing a new value
+pub struct DragValue<'a, N: Numeric> {
+    value: &'a mut N,
+    range: RangeInclusive<N>,
+    speed: Option<f64>,
+    decimals: Option<usize>,
+    prefix: &'a str,
+    suffix: &'a str,
+    min_width: u16,
+    formatter: Option<Formatter<'a>>,
+    parser: Option<Parser<'a>>,
+    style: Option<Style>,
+}
+
+impl<'a, N: Numeric> DragValue<'a, N> {
+    pub fn new(value: &'a mut N) -> Self {
+        Self {
+            value,
+            range: N::MIN..=N::MAX,
+            speed: None,
+            decimals: None,
+            prefix: "",
+            suffix: "",
+            min_width: 3,
+            formatter: None,
+            parser: None,
+            style: None,
+        }
+    }
+
+    /// Values are clamped into `range`
+    pub fn range(mut self, range: RangeInclusive<N>) -> Self {
+        self.range = range;
+        self
+    }
+
+    /// How much one cell of dragging or one key press changes the value,
+    /// defaults to 1 for integers and 0.1 for floats
+    pub fn speed(mut self, speed: f64) -> Self {
+        self.speed = Some(speed.abs());
+        self
+    }
+
+    pub fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = Some(decimals);
+        self
+    }
+
+    pub fn prefix(mut self, prefix: &'a str) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    pub fn suffix(mut self, suffix: &'a str) -> Self {
+        self.suffix = suffix;
+        self
+    }
+
+    pub fn min_width(mut self, min_width: u16) -> Self {
+        self.min_width = min_width;
+        self
+    }
+
+    pub fn custom_formatter(mut self, formatter: impl Fn(f64) -> String + 'a) -> Self {
+        self.formatter = Some(Box::new(formatter));
+        self
+    }
+
+    /// Parses typed text, the default accepts anything `f64` parses
+    pub fn custom_parser(mut self, parser: impl Fn(&str) -> Option<f64> + 'a) -> Self {
+        self.parser = Some(Box::new(parser));
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    fn set(&mut self, value: f64) {
+        let (min, max) = (self.range.start().to_f64(), self.range.end().to_f64());
+        if !value.is_nan() {
+            *self.value = N::from_f64(value.clamp(min.min(max), max.max(min)));
+        }
+    }
+
+    fn format(&self) -> String {
+        let value = self.value.to_f64();
+        match &self.formatter {
+            Some(formatter) => formatter(value),
+            None => format_value(value, N::INTEGRAL, self.decimals),
+        }
+    }
+
+    fn commit(&mut self, text: &str) {
+        let parsed = match &self.parser {
+            Some(parser) => parser(text),
+            None => text.trim().parse::<f64>().ok(),
+        };
+        if let Some(value) = parsed {
+            self.set(value);
+        }
+    }
+
+    pub fn show(mut self, ui: &mut Ui) -> Response {
+        let id = Id::new(ui.next_id_source());
+        let ctx = ui.ctx().clone();
+        let default_style = *ctx.style().borrow();
+        let mut state = ctx.get_memory_or(id, DragValueState::default());
+
+        let formatted = format!("{}{}{}", self.prefix, self.format(), self.suffix);
+        let width = match &state.editing {
+            Some((text, _)) => str_width(text, None) + 1,
+            None => str_width(&formatted, None),
+        }
+        .max(self.min_width);
+        let area = ui.allocate_size(VecI2::new(width, 1));
+        let mut response = ui.interact(id, area);
+        let before = *self.value;
+        let mut group = None;
+        let speed = self.speed.unwrap_or(if N::INTEGRAL { 1.0 } else { 0.1 });
+
+        let mouse = ctx.input().mouse;
+        let keyboard = &ctx.input().keyboard;
+        if let Some((mut text, mut cursor)) = state.editing.take() {
+            ctx.lock_focus(FocusLock {
+                horizontal: true,
+                vertical: true,
+                tab: false,
+            });
+
+            let clicked_outside =
+                matches!(mouse.buttons[0], MouseButtonState::Down(pos) if !area.contains(pos));
+            let mut done = !response.focused() || clicked_outside;
+            let mut cancel = false;
+            for key in &keyboard.events {
+                match key.code {
+                    KeyCode::Enter | KeyCode::Tab => done = true,
+                    KeyCode::Esc => cancel = true,
+                    _ => {
+                        apply_key(&mut text, &mut cursor, key, None);
+                    }
+                }
+            }
+            if let Some(paste) = &response.paste {
+                insert_text(&mut text, &mut cursor, paste.trim(), None);
+            }
+
+            if cancel {
+                response.surrender_focus(&ctx);
+            } else if done {
+                self.commit(&text);
+                group = Some(UndoGroup::Typing);
+                if clicked_outside {
+                    response.surrender_focus(&ctx);
+                }
+            } else {
+                state.editing = Some((text, cursor));
+            }
+        } else {
+            if matches!(mouse.buttons[0], MouseButtonState::Down(pos) if area.contains(pos))
+                && response.pressed()
+            {
+                response.request_focus(&ctx);
+            }
+            if response.dragged() {
+                // right and up increase the value
+                let (dx, dy) = response.drag_delta();
+                if dx != 0 || dy != 0 {
+                    self.set(self.value.to_f64() + (dx - dy) as f64 * speed);
+                    group = Some(UndoGroup::Dragging);
+                }
+            }
+
+            let released_without_drag =
+                matches!(response.buttons[0], MouseButtonState::Released(_));
+            let mut start_editing = released_without_drag.then(String::new);
+
+            if response.focused() {
+                ctx.lock_focus(FocusLock {
+                    vertical: true,
+                    ..Default::default()
+                });
+                for key in &keyboard.events {
+                    let step = if key.modifiers.contains(KeyModifiers::SHIFT) {
+                        speed * 10.0
+                    } else {
+                        speed
+                    };
+                    match key.code {
+                        KeyCode::Up => self.set(self.value.to_f64() + step),
+                        KeyCode::Down => self.set(self.value.to_f64() - step),
+                        KeyCode::Enter => start_editing = Some(String::new()),
+                        // typing a number starts editing with a fresh value
+                        KeyCode::Char(c)
+                            if (c.is_ascii_digit() || c == '-' || c == '.')
+                                && !key.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            start_editing = Some(c.to_string())
+                        }
+                        _ => continue,
+                    }
+                    if start_editing.is_none() {
+                        group = Some(UndoGroup::Changed);
+                    }
+                }
+            }
+
+            if let Some(initial) = start_editing {
+                response.request_focus(&ctx);
+                // editing the current value selects it so typing replaces it
+                let (text, anchor) = if initial.is_empty() {
+                    (self.format(), 0)
+                } else {
+                    (initial.clone(), initial.len())
+                };
+                let cursor = TextCursor {
+                    anchor,
+                    cursor: text.len(),
+                };
+                state.editing = Some((text, cursor));
+            }
+        }
+
+        if undo_value(&ctx, id, response.focused(), before, self.value, group) {
+            response.mark_changed();
+        }
+        if *self.value != before {
+            response.mark_changed();
+        }
+
+        let mut style = self.style.unwrap_or(if response.focused() {
+            default_style.text_edit_focused
+        } else {
+            default_style.text_edit
+        });
+        match &state.editing {
+            Some((text, cursor)) => {
+                let caret = str_width(&text[..cursor.cursor], None);
+                let scroll = (caret + 1).saturating_sub(area.width);
+                draw_line(
+                    ui,
+                    text,
+                    0,
+                    area,
+                    scroll,
+                    cursor.selection(),
+                    None,
+                    0,
+                    style,
+                    default_style.text_selection,
+                );
+                ctx.set_cursor(Cursor {
+                    x: area.x + caret - scroll,
+                    y: area.y,
+                });
+            }
+            None => {
+                if response.hovered() {
+                    style.attributes.set(crate::style::Attribute::Underlined);
+                }
+                let formatted = format!("{}{}{}", self.prefix, self.format(), self.suffix);
+                draw_line(ui, &formatted, 0, area, 0, 0..0, None, 0, style, style);
+            }
+        }
+
+        ctx.insert_into_memory(id, state);
+        response
+    }
+}
@@ -1,5 +1,15 @@
+pub mod button;
+pub mod checkbox;
+pub mod combo_box;
+pub mod drag_value;
+pub mod lable;
 pub mod progress_bar;
-pub mod spinner;
+pub mod radio_button;
 pub mod seperator;
-pub mod button;
-pub mod lable;
\ No newline at end of file
+pub mod slider;
+pub mod spinner;
+pub mod table;
+pub mod text_area;
+pub mod text_edit;
+pub mod toggle;
+pub mod tree_view;
@@ -6,6 +6,17 @@ use crate::{
     ui::{Layout, Ui},
 };
 
+/// Splits `len` cells filled to `progress` into the whole cells, the eighths of the
+/// partly filled cell and the empty cells
+pub(crate) fn eighths(len: u16, progress: f32) -> (u32, u32, u32) {
+    let complete = (len as f32 * progress.clamp(0.0, 1.0) * 8.0) as u32;
+    (
+        complete / 8,
+        complete % 8,
+        ((len as u32 * 8) - complete) / 8,
+    )
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct ProgressBar {
     pub style: Option<Style>,
@@ -82,9 +93,7 @@ impl ProgressBar {
             (rect.height, rect)
         };
 
-        let complete = (len as f32 * progress.clamp(0.0, 1.0) * 8.0) as u32;
-        let whole = complete / 8;
-        let remaining = ((len as u32 * 8) - complete) / 8;
+        let (whole, partial, remaining) = eighths(len, progress);
 
         let full = if layout.is_primary_vertical() {
             ui.ctx().style().borrow().blocks.full
@@ -113,33 +122,9 @@ impl ProgressBar {
 
         if whole + remaining != len as u32 {
             let t = if layout.is_primary_horizontal() {
-                let bars = ui.ctx().style().borrow().blocks;
-                match complete % 8 {
-                    0 => bars.empty,
-                    1 => bars.one_eighth,
-                    2 => bars.one_quarter,
-                    3 => bars.three_eighths,
-                    4 => bars.half,
-                    5 => bars.five_eighths,
-                    6 => bars.three_quarters,
-                    7 => bars.seven_eighths,
-                    // not gonna happen
-                    _ => bars.empty,
-                }
+                ui.ctx().style().borrow().blocks.eighths(partial)
             } else {
-                let bars = ui.ctx().style().borrow().bars;
-                match complete % 8 {
-                    0 => bars.empty,
-                    1 => bars.one_eighth,
-                    2 => bars.one_quarter,
-                    3 => bars.three_eighths,
-                    4 => bars.half,
-                    5 => bars.five_eighths,
-                    6 => bars.three_quarters,
-                    7 => bars.seven_eighths,
-                    // not gonna happen
-                    _ => bars.empty,
-                }
+                ui.ctx().style().borrow().bars.eighths(partial)
             };
 
             string.push_str(t);
@@ -0,0 +1,30 @@
+use crate::{response::Response, style::StyledText, ui::Ui};
+
+use super::checkbox::show_glyph_label;
+
+/// One option of a group, see [`Ui::radio_value`] for binding a group to a value
+pub struct RadioButton<'a> {
+    selected: bool,
+    text: StyledText<'a>,
+}
+
+impl<'a> RadioButton<'a> {
+    pub fn new(selected: bool, text: impl Into<StyledText<'a>>) -> Self {
+        Self {
+            selected,
+            text: text.into(),
+        }
+    }
+
+    /// `clicked()` tells if the user picked this option
+    pub fn show(self, ui: &mut Ui) -> Response {
+        let radios = ui.ctx().style().borrow().radios;
+        let glyph = if self.selected {
+            radios.selected
+        } else {
+            radios.unselected
+        };
+
+        show_glyph_label(ui, glyph, false, &self.text)
+    }
+}
@@ -0,0 +1,383 @@
+use std::{cell::RefCell, ops::RangeInclusive, rc::Rc};
+
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::{
+    context::{Context, Cursor, FocusLock},
+    id::Id,
+    input::mouse::MouseButtonState,
+    math_util::{Numeric, Rect, VecI2},
+    response::Response,
+    style::{Style, StyledText},
+    ui::Ui,
+    undo::{UndoGroup, Undoer},
+    widgets::progress_bar::eighths,
+};
+
+/// Formats `value` for display, integers never show decimals
+pub(crate) fn format_value(value: f64, integral: bool, decimals: Option<usize>) -> String {
+    match decimals {
+        _ if integral => format!("{}", value.round()),
+        Some(decimals) => format!("{value:.decimals$}"),
+        None => {
+            // enough digits to not hide small changes but no trailing noise
+            let text = format!("{value:.3}");
+            let text = text.trim_end_matches('0').trim_end_matches('.');
+            if text == "-0" {
+                "0".to_owned()
+            } else {
+                text.to_owned()
+            }
+        }
+    }
+}
+
+/// Symmetric log mapping that also works for ranges touching or crossing zero
+fn log_map(value: f64) -> f64 {
+    value.signum() * value.abs().ln_1p()
+}
+
+fn log_unmap(value: f64) -> f64 {
+    value.signum() * value.abs().exp_m1()
+}
+
+/// Position of `value` in `range` from 0 to 1
+pub(crate) fn normalize(value: f64, range: &RangeInclusive<f64>, logarithmic: bool) -> f64 {
+    let (min, max, value) = if logarithmic {
+        (
+            log_map(*range.start()),
+            log_map(*range.end()),
+            log_map(value),
+        )
+    } else {
+        (*range.start(), *range.end(), value)
+    };
+    if max == min {
+        0.0
+    } else {
+        ((value - min) / (max - min)).clamp(0.0, 1.0)
+    }
+}
+
+pub(crate) fn denormalize(t: f64, range: &RangeInclusive<f64>, logarithmic: bool) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+    if logarithmic {
+        let (min, max) = (log_map(*range.start()), log_map(*range.end()));
+        log_unmap(min + (max - min) * t)
+    } else {
+        range.start() + (range.end() - range.start()) * t
+    }
+}
+
+/// Loads the undo history of a numeric widget when it could be used, it is
+/// shared in memory so it is not copied every frame
+pub(crate) fn undo_value<N: Numeric>(
+    ctx: &Context,
+    id: Id,
+    focused: bool,
+    before: N,
+    value: &mut N,
+    group: Option<UndoGroup>,
+) -> bool {
+    let edited = before != *value;
+    if !focused && !edited {
+        return false;
+    }
+    let undoer = ctx.get_memory_or_create(id, || Rc::new(RefCell::new(Undoer::<N>::default())));
+    let mut undoer = undoer.borrow_mut();
+    if edited {
+        undoer.record(before, group);
+    }
+    focused && undoer.apply_shortcuts(ctx, value)
+}
+
+pub type Formatter<'a> = Box<dyn Fn(f64) -> String + 'a>;
+
+/// A horizontal or vertical slider for a number in a range, drawn with eighth
+/// block glyphs so the position is shown with sub cell precision
+pub struct Slider<'a, N: Numeric> {
+    value: &'a mut N,
+    range: RangeInclusive<N>,
+    vertical: bool,
+    length: u16,
+    step: Option<f64>,
+    coarse_step: Option<f64>,
+    logarithmic: bool,
+    show_value: bool,
+    decimals: Option<usize>,
+    formatter: Option<Formatter<'a>>,
+    text: StyledText<'a>,
+    style: Option<Style>,
+}
+
+impl<'a, N: Numeric> Slider<'a, N> {
+    pub fn new(value: &'a mut N, range: RangeInclusive<N>) -> Self {
+        Self {
+            value,
+            range,
+            vertical: false,
+            length: 20,
+            step: None,
+            coarse_step: None,
+            logarithmic: false,
+            show_value: true,
+            decimals: None,
+            formatter: None,
+            text: StyledText::default(),
+            style: None,
+        }
+    }
+
+    pub fn vertical(mut self) -> Self {
+        self.vertical = true;
+        self
+    }
+
+    /// Size of the track in cells
+    pub fn length(mut self, length: u16) -> Self {
+        self.length = length.max(1);
+        self
+    }
+
+    /// Values snap to multiples of `step` and the arrow keys move by it
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = Some(step.abs()).filter(|step| *step > 0.0);
+        self
+    }
+
+    /// How far shift+arrow and page up/down move, defaults to ten steps
+    pub fn coarse_step(mut self, coarse_step: f64) -> Self {
+        self.coarse_step = Some(coarse_step.abs()).filter(|step| *step > 0.0);
+        self
+    }
+
+    pub fn logarithmic(mut self, logarithmic: bool) -> Self {
+        self.logarithmic = logarithmic;
+        self
+    }
+
+    pub fn show_value(mut self, show_value: bool) -> Self {
+        self.show_value = show_value;
+        self
+    }
+
+    pub fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = Some(decimals);
+        self
+    }
+
+    pub fn custom_formatter(mut self, formatter: impl Fn(f64) -> String + 'a) -> Self {
+        self.formatter = Some(Box::new(formatter));
+        self
+    }
+
+    /// Label drawn after the value
+    pub fn text(mut self, text: impl Into<StyledText<'a>>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    fn snap(&self, value: f64, range: &RangeInclusive<f64>) -> N {
+        let value = match self.step {
+            Some(step) => range.start() + ((value - range.start()) / step).round() * step,
+            None => value,
+        };
+        N::from_f64(value.clamp(*range.start(), *range.end()))
+    }
+
+    pub fn show(self, ui: &mut Ui) -> Response {
+        if self.vertical {
+            ui.vertical(|ui| self.show_inner(ui))
+        } else {
+            ui.horizontal(|ui| self.show_inner(ui))
+        }
+    }
+
+    fn show_inner(self, ui: &mut Ui) -> Response {
+        let id = Id::new(ui.next_id_source());
+        let ctx = ui.ctx().clone();
+        let default_style = *ctx.style().borrow();
+
+        let (min, max) = (self.range.start().to_f64(), self.range.end().to_f64());
+        let range = min.min(max)..=max.max(min);
+
+        let track = ui.allocate_size(if self.vertical {
+            VecI2::new(1, self.length)
+        } else {
+            VecI2::new(self.length, 1)
+        });
+        let len = self.length.min(if self.vertical {
+            track.height
+        } else {
+            track.width
+        });
+        let mut response = ui.interact(id, track);
+        let before = *self.value;
+        let mut group = None;
+
+        // mouse position along the track, the first and last cells are the ends of the range
+        let t_at = |pos: VecI2| {
+            let along = if self.vertical {
+                (track.y + len).saturating_sub(pos.y + 1)
+            } else {
+                pos.x.saturating_sub(track.x)
+            };
+            along as f64 / (len.max(2) - 1) as f64
+        };
+
+        let mouse = ctx.input().mouse;
+        match (mouse.buttons[0], mouse.position) {
+            (MouseButtonState::Down(pos), _) if track.contains(pos) && response.pressed() => {
+                response.request_focus(&ctx);
+                *self.value = self.snap(denormalize(t_at(pos), &range, self.logarithmic), &range);
+                group = Some(UndoGroup::Dragging);
+            }
+            (MouseButtonState::Drag { .. }, Some(pos)) if response.dragged() => {
+                *self.value = self.snap(denormalize(t_at(pos), &range, self.logarithmic), &range);
+                group = Some(UndoGroup::Dragging);
+            }
+            _ => {}
+        }
+
+        if response.focused() {
+            ctx.lock_focus(FocusLock {
+                horizontal: !self.vertical,
+                vertical: self.vertical,
+                tab: false,
+            });
+
+            for key in &ctx.input().keyboard.events {
+                let coarse = key.modifiers.contains(KeyModifiers::SHIFT);
+                let direction: f64 = match key.code {
+                    KeyCode::Right | KeyCode::Up => 1.0,
+                    KeyCode::Left | KeyCode::Down => -1.0,
+                    KeyCode::PageUp => 10.0,
+                    KeyCode::PageDown => -10.0,
+                    KeyCode::Home => {
+                        *self.value = self.snap(*range.start(), &range);
+                        continue;
+                    }
+                    KeyCode::End => {
+                        *self.value = self.snap(*range.end(), &range);
+                        continue;
+                    }
+                    _ => continue,
+                };
+                let direction = if coarse { direction * 10.0 } else { direction };
+                group = Some(UndoGroup::Changed);
+
+                let value = self.value.to_f64();
+                let new = match (self.step, self.coarse_step) {
+                    (_, Some(coarse_step)) if direction.abs() >= 10.0 => {
+                        value + coarse_step * direction.signum()
+                    }
+                    (Some(step), _) => value + step * direction,
+                    (None, _) if N::INTEGRAL && !self.logarithmic => value + direction,
+                    // without a step move by a hundredth of the track
+                    (None, _) => {
+                        let t = normalize(value, &range, self.logarithmic) + direction / 100.0;
+                        denormalize(t, &range, self.logarithmic)
+                    }
+                };
+                let mut snapped = self.snap(new, &range);
+                // integer rounding can swallow a small step, always move at least one
+                if snapped == *self.value && N::INTEGRAL {
+                    snapped = self.snap(value + direction.signum(), &range);
+                }
+                *self.value = snapped;
+            }
+        }
+
+        if undo_value(&ctx, id, response.focused(), before, self.value, group) {
+            response.mark_changed();
+        }
+        if *self.value != before {
+            response.mark_changed();
+        }
+
+        // draw the filled part of the track with eighth precision
+        let mut style = self.style.unwrap_or(default_style.button_active);
+        if response.hovered() {
+            style.attributes.set(crate::style::Attribute::Underlined);
+        }
+        let t = normalize(self.value.to_f64(), &range, self.logarithmic);
+        let (whole, partial, _) = eighths(len, t as f32);
+        for i in 0..len {
+            let glyph = match (i as u32).cmp(&whole) {
+                std::cmp::Ordering::Less => {
+                    if self.vertical {
+                        default_style.bars.full
+                    } else {
+                        default_style.blocks.full
+                    }
+                }
+                std::cmp::Ordering::Equal if self.vertical => default_style.bars.eighths(partial),
+                std::cmp::Ordering::Equal => default_style.blocks.eighths(partial),
+                std::cmp::Ordering::Greater => " ",
+            };
+            let pos = if self.vertical {
+                VecI2::new(track.x, track.y + len.saturating_sub(1) - i)
+            } else {
+                VecI2::new(track.x + i, track.y)
+            };
+            ui.draw(glyph, style, pos, Rect::new_pos_size(pos, VecI2::new(1, 1)));
+        }
+
+        if response.focused() {
+            let handle = (whole as u16).min(len.saturating_sub(1));
+            ctx.set_cursor(if self.vertical {
+                Cursor {
+                    x: track.x,
+                    y: track.y + len.saturating_sub(1) - handle,
+                }
+            } else {
+                Cursor {
+                    x: track.x + handle,
+                    y: track.y,
+                }
+            });
+        }
+
+        if self.show_value {
+            let value = self.value.to_f64();
+            let text = match &self.formatter {
+                Some(formatter) => formatter(value),
+                None => format_value(value, N::INTEGRAL, self.decimals),
+            };
+            if !self.vertical {
+                ui.add_space_primary_direction(1);
+            }
+            ui.label(text);
+        }
+        if !self.text.text.is_empty() {
+            if !self.vertical {
+                ui.add_space_primary_direction(1);
+            }
+            ui.label(self.text);
+        }
+
+        response
+    }
+}
+
+#[test]
+pub fn test_slider_mapping() {
+    let range = 0.0..=100.0;
+    assert_eq!(normalize(25.0, &range, false), 0.25);
+    assert_eq!(denormalize(0.25, &range, false), 25.0);
+
+    let range = -1000.0..=1000.0;
+    let t = normalize(10.0, &range, true);
+    assert!(t > 0.5 && t < 0.75);
+    assert!((denormalize(t, &range, true) - 10.0).abs() < 1e-9);
+
+    assert_eq!(format_value(2.5, false, None), "2.5");
+    assert_eq!(format_value(2.0, false, None), "2");
+    assert_eq!(format_value(2.6, true, None), "3");
+    assert_eq!(format_value(2.0, false, Some(2)), "2.00");
+}
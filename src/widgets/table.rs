@@ -0,0 +1,444 @@
+use std::{cmp::Ordering, rc::Rc};
+
+use crate::{
+    containers::list_view::{ListView, ListViewState, Selection, SelectionMode},
+    id::Id,
+    input::mouse::MouseButtonState,
+    math_util::{Rect, VecI2},
+    response::Response,
+    style::Style,
+    ui::{Layout, Ui},
+};
+
+use super::text_edit::str_width;
+
+/// How much room a column gets
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColumnWidth {
+    /// exactly this many cells
+    Fixed(u16),
+    /// a share of what the other columns leave over, relative to the other fractions
+    Fraction(f32),
+    /// as wide as the header and the widest row seen so far
+    Auto,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+type CellText<'a, T> = Box<dyn Fn(&T) -> String + 'a>;
+type Compare<'a, T> = Box<dyn Fn(&T, &T) -> Ordering + 'a>;
+/// column and direction the rows are sorted by
+type Sort = Option<(usize, SortOrder)>;
+
+/// A column of a [`Table`] showing one field of `T`
+pub struct Column<'a, T> {
+    title: String,
+    width: ColumnWidth,
+    resizable: bool,
+    text: CellText<'a, T>,
+    compare: Option<Compare<'a, T>>,
+}
+
+impl<'a, T> Column<'a, T> {
+    pub fn new(title: impl Into<String>, text: impl Fn(&T) -> String + 'a) -> Self {
+        Self {
+            title: title.into(),
+            width: ColumnWidth::Auto,
+            resizable: true,
+            text: Box::new(text),
+            compare: None,
+        }
+    }
+
+    pub fn width(mut self, width: ColumnWidth) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Dragging the line right of the header changes the width, on by default
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    /// Clicking the header sorts the rows with `compare`
+    pub fn sort_by(mut self, compare: impl Fn(&T, &T) -> Ordering + 'a) -> Self {
+        self.compare = Some(Box::new(compare));
+        self
+    }
+
+    pub fn sort_by_key<K: Ord>(self, key: impl Fn(&T) -> K + 'a) -> Self {
+        self.sort_by(move |a, b| key(a).cmp(&key(b)))
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TableState {
+    /// widths set by dragging, these override the column width
+    pub resized: Vec<Option<u16>>,
+    /// widest cell seen so far of the auto columns
+    pub measured: Vec<u16>,
+    pub sort: Option<(usize, SortOrder)>,
+    /// column being resized and its width when the drag started
+    pub resizing: Option<(usize, u16)>,
+    /// the sorted order and the sort and row count it was made for
+    order: Option<(Sort, usize, Rc<[usize]>)>,
+}
+
+pub struct TableResponse {
+    /// the response of the rows
+    pub response: Response,
+    /// positions of the selected rows as shown, see [`TableResponse::selected_rows`]
+    pub selection: Selection,
+    /// the row the keyboard cursor is on
+    pub cursor: Option<usize>,
+    /// row enter was pressed on or that was double clicked
+    pub activated: Option<usize>,
+    pub sort: Option<(usize, SortOrder)>,
+    order: Option<Rc<[usize]>>,
+}
+
+impl TableResponse {
+    /// Index into the rows of the row shown at `position`
+    pub fn row_at(&self, position: usize) -> usize {
+        self.order
+            .as_ref()
+            .map_or(position, |order| order[position])
+    }
+
+    /// Indices into the rows of the selected rows, in the order they are shown
+    pub fn selected_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        self.selection.iter().map(|position| self.row_at(position))
+    }
+}
+
+/// Splits `available` cells between columns, `measured` is what auto columns need.
+/// Fixed and auto columns come first, fractions share the rest and the last
+/// columns are cut when there is not enough room.
+pub(crate) fn layout_widths(available: u16, widths: &[(ColumnWidth, u16)]) -> Vec<u16> {
+    let wanted: Vec<u16> = widths
+        .iter()
+        .map(|(width, measured)| match width {
+            ColumnWidth::Fixed(width) => *width,
+            ColumnWidth::Auto => *measured,
+            ColumnWidth::Fraction(_) => 0,
+        })
+        .collect();
+    let taken = wanted
+        .iter()
+        .fold(0u16, |sum, width| sum.saturating_add(*width));
+    let left = available.saturating_sub(taken);
+    let total_fraction: f32 = widths
+        .iter()
+        .filter_map(|(width, _)| match width {
+            ColumnWidth::Fraction(fraction) => Some(fraction.max(0.0)),
+            _ => None,
+        })
+        .sum();
+
+    let mut result = wanted;
+    if total_fraction > 0.0 {
+        let mut given = 0;
+        let mut last = None;
+        for (i, (width, _)) in widths.iter().enumerate() {
+            if let ColumnWidth::Fraction(fraction) = width {
+                result[i] = (left as f32 * fraction.max(0.0) / total_fraction) as u16;
+                given += result[i];
+                last = Some(i);
+            }
+        }
+        // rounding leftovers go to the last fraction
+        if let Some(last) = last {
+            result[last] += left - given;
+        }
+    }
+
+    let mut room = available;
+    for width in &mut result {
+        *width = (*width).max(1).min(room);
+        room -= *width;
+    }
+    result
+}
+
+/// Rows of `T` in columns with a header. Only the rows in view are drawn so
+/// tables of any size stay fast. Headers of sortable columns sort when clicked,
+/// the lines between headers can be dragged to resize the columns, and rows are
+/// selected like in a [`ListView`].
+pub struct Table<'a, T> {
+    id_source: Id,
+    columns: Vec<Column<'a, T>>,
+    mode: SelectionMode,
+    stick_to_bottom: bool,
+    size: Option<VecI2>,
+    resort: bool,
+}
+
+impl<'a, T> Table<'a, T> {
+    pub fn new(id_source: impl std::hash::Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            columns: Vec::new(),
+            mode: SelectionMode::default(),
+            stick_to_bottom: false,
+            size: None,
+            resort: false,
+        }
+    }
+
+    pub fn column(mut self, column: Column<'a, T>) -> Self {
+        self.columns.push(column);
+        self
+    }
+
+    pub fn selection_mode(mut self, mode: SelectionMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Keeps showing the last rows as more are added while scrolled to the end
+    pub fn stick_to_bottom(mut self, stick: bool) -> Self {
+        self.stick_to_bottom = stick;
+        self
+    }
+
+    /// Defaults to all the space available
+    pub fn desired_size(mut self, size: VecI2) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// The sorted order is kept until the sort or the number of rows changes,
+    /// set this when rows were edited in place
+    pub fn resort(mut self, resort: bool) -> Self {
+        self.resort = resort;
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui, rows: &[T]) -> TableResponse {
+        let ctx = ui.ctx().clone();
+        let id = ui.id().with(self.id_source);
+        let default_style = *ctx.style().borrow();
+        let lines = default_style.lines;
+        let rows_source = id.with("rows");
+        let mut state = ctx.get_memory_or(id, TableState::default());
+        state.resized.resize(self.columns.len(), None);
+        state.measured.resize(self.columns.len(), 0);
+
+        let size = self.size.unwrap_or_else(|| ui.get_max().size());
+        let area = ui.allocate_size(size);
+        let mut table_ui = Ui::new(ctx.clone(), Layout::TopLeftVertical, id, area, ui.layer());
+        let body_height = area.height.saturating_sub(2);
+        let list_id = ListView::id(&table_ui, rows_source);
+        // the rows get a scrollbar on the right when they do not fit
+        let rows_width = area.width - (rows.len() > body_height as usize && area.width > 1) as u16;
+
+        // sorting a lot of rows is slow so the order is only made again when needed
+        let sorted = state.sort.filter(|(column, _)| {
+            self.columns
+                .get(*column)
+                .is_some_and(|column| column.compare.is_some())
+        });
+        let order = match &state.order {
+            Some((sort, len, order)) if *sort == sorted && *len == rows.len() && !self.resort => {
+                Some(order.clone())
+            }
+            _ => sorted.map(|(column, sort_order)| {
+                let compare = self.columns[column].compare.as_ref().unwrap();
+                let mut order: Vec<usize> = (0..rows.len()).collect();
+                order.sort_by(|a, b| {
+                    let ordering = compare(&rows[*a], &rows[*b]);
+                    match sort_order {
+                        SortOrder::Ascending => ordering,
+                        SortOrder::Descending => ordering.reverse(),
+                    }
+                });
+                Rc::from(order)
+            }),
+        };
+        state.order = order.clone().map(|order| (sorted, rows.len(), order));
+        let row_at = |position: usize| order.as_ref().map_or(position, |order| order[position]);
+
+        // auto columns grow to fit the rows in view
+        let first = ctx.get_memory_or(list_id, ListViewState::default()).scroll;
+        for (i, column) in self.columns.iter().enumerate() {
+            if column.width != ColumnWidth::Auto {
+                continue;
+            }
+            let header = str_width(&column.title, None) + 2;
+            let cells = (first..rows.len())
+                .take(body_height as usize)
+                .map(|position| str_width(&(column.text)(&rows[row_at(position)]), None))
+                .max()
+                .unwrap_or(0);
+            state.measured[i] = state.measured[i].max(header).max(cells);
+        }
+        let widths: Vec<(ColumnWidth, u16)> = self
+            .columns
+            .iter()
+            .zip(&state.resized)
+            .zip(&state.measured)
+            .map(|((column, resized), measured)| {
+                (resized.map_or(column.width, ColumnWidth::Fixed), *measured)
+            })
+            .collect();
+        let separators = self.columns.len().saturating_sub(1) as u16;
+        let widths = layout_widths(rows_width.saturating_sub(separators), &widths);
+        let mut starts = Vec::with_capacity(widths.len());
+        let mut x = area.x;
+        for width in &widths {
+            starts.push(x);
+            x += width + 1;
+        }
+
+        // header
+        let header = table_ui.allocate_size(VecI2::new(area.width, 1));
+        let mut sort_clicked = None;
+        for (i, column) in self.columns.iter().enumerate() {
+            let cell =
+                Rect::new_pos_size(VecI2::new(starts[i], header.y), VecI2::new(widths[i], 1));
+            let style = if column.compare.is_some() {
+                let response = table_ui.interact(id.with(("header", i)), cell);
+                if response.clicked() {
+                    sort_clicked = Some(i);
+                }
+                if response.hovered() {
+                    default_style.button_hovered
+                } else if response.focused() {
+                    default_style.button_focused
+                } else {
+                    default_style.button
+                }
+            } else {
+                default_style.button
+            };
+            let indicator = match state.sort {
+                Some((column, SortOrder::Ascending)) if column == i => default_style.pointers.up,
+                Some((column, SortOrder::Descending)) if column == i => default_style.pointers.down,
+                _ => "",
+            };
+            let indicator_width = str_width(indicator, None);
+            let title_width = str_width(&column.title, None);
+            let padding = widths[i].saturating_sub(title_width + indicator_width) as usize;
+            let text = format!("{}{}{indicator}", column.title, " ".repeat(padding));
+            table_ui.draw(&text, style, cell.top_left(), cell);
+
+            if i + 1 < self.columns.len() {
+                let line =
+                    Rect::new_pos_size(VecI2::new(cell.x + cell.width, header.y), VecI2::new(1, 1));
+                table_ui.draw(lines.vertical, default_style.button, line.top_left(), line);
+                if column.resizable {
+                    let handle = table_ui.interact_mouse(id.with(("resize", i)), line);
+                    match handle.buttons[0] {
+                        MouseButtonState::Down(_) => state.resizing = Some((i, widths[i])),
+                        MouseButtonState::Drag { start, current } => {
+                            if let Some((column, width)) =
+                                state.resizing.filter(|(column, _)| *column == i)
+                            {
+                                let moved = current.x as i32 - start.x as i32;
+                                state.resized[column] = Some((width as i32 + moved).max(1) as u16);
+                            }
+                        }
+                        MouseButtonState::DragReleased { .. } | MouseButtonState::Released(_) => {
+                            state.resizing = None
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        // line under the header with junctions where the column lines meet it
+        let underline = table_ui.allocate_size(VecI2::new(area.width, 1));
+        let mut cells = vec![lines.horizontal; underline.width as usize];
+        for start in starts.iter().skip(1) {
+            if let Some(cell) = cells.get_mut((start - 1 - area.x) as usize) {
+                *cell = lines.cross;
+            }
+        }
+        let text = cells.concat();
+        table_ui.draw(&text, default_style.button, underline.top_left(), underline);
+
+        if let Some(column) = sort_clicked {
+            state.sort = match state.sort {
+                Some((sorted, SortOrder::Ascending)) if sorted == column => {
+                    Some((column, SortOrder::Descending))
+                }
+                Some((sorted, SortOrder::Descending)) if sorted == column => None,
+                _ => Some((column, SortOrder::Ascending)),
+            };
+            // positions mean other rows now
+            let mut list_state: ListViewState =
+                ctx.get_memory_or(list_id, ListViewState::default());
+            list_state.selection.clear();
+            list_state.cursor = 0;
+            list_state.anchor = 0;
+            ctx.insert_into_memory(list_id, list_state);
+        }
+
+        let list = ListView::new(rows_source, rows.len())
+            .selection_mode(self.mode)
+            .stick_to_bottom(self.stick_to_bottom)
+            .desired_size(VecI2::new(area.width, body_height))
+            .show(&mut table_ui, |ui, row| {
+                let style = if row.selected {
+                    default_style.list_highlight
+                } else {
+                    Style::default()
+                };
+                let item = &rows[row_at(row.index)];
+                let y = ui.get_max().y;
+                for (i, column) in self.columns.iter().enumerate() {
+                    let cell =
+                        Rect::new_pos_size(VecI2::new(starts[i], y), VecI2::new(widths[i], 1));
+                    ui.draw(&(column.text)(item), style, cell.top_left(), cell);
+                    if i + 1 < self.columns.len() {
+                        let line = Rect::new_pos_size(
+                            VecI2::new(cell.x + cell.width, y),
+                            VecI2::new(1, 1),
+                        );
+                        ui.draw(lines.vertical, default_style.button, line.top_left(), line);
+                    }
+                }
+            });
+
+        let response = TableResponse {
+            response: list.response,
+            selection: list.selection,
+            cursor: (!rows.is_empty()).then(|| row_at(list.cursor)),
+            activated: list.activated.map(row_at),
+            sort: state.sort,
+            order,
+        };
+        ctx.insert_into_memory(id, state);
+        response
+    }
+}
+
+#[test]
+pub fn test_layout_widths() {
+    use ColumnWidth::*;
+
+    assert_eq!(
+        layout_widths(20, &[(Fixed(5), 0), (Auto, 7), (Fixed(3), 0)]),
+        vec![5, 7, 3]
+    );
+    // fractions share what is left, leftovers go to the last one
+    assert_eq!(
+        layout_widths(20, &[(Fixed(4), 0), (Fraction(1.0), 0), (Fraction(2.0), 0)]),
+        vec![4, 5, 11]
+    );
+    assert_eq!(
+        layout_widths(10, &[(Fraction(1.0), 0), (Fraction(1.0), 0)]),
+        vec![5, 5]
+    );
+    // too little room cuts the last columns
+    assert_eq!(
+        layout_widths(8, &[(Fixed(5), 0), (Auto, 7), (Fraction(1.0), 0)]),
+        vec![5, 3, 0]
+    );
+}
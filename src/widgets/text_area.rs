@@ -0,0 +1,402 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::{
+    context::{Cursor, FocusLock},
+    id::Id,
+    input::mouse::MouseButtonState,
+    math_util::{Rect, VecI2},
+    response::Response,
+    style::{Style, StyledText},
+    ui::Ui,
+};
+
+use super::text_edit::{
+    apply_clipboard_key, apply_key, apply_undo_key, draw_line, index_at_column, insert_text,
+    line_width, load_undoer, undo_group, ClipboardEdit, TextCursor,
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TextAreaState {
+    pub cursor: TextCursor,
+    /// how many cells are scrolled out of view to the left
+    pub scroll_x: u16,
+    /// the first visible line
+    pub scroll_y: usize,
+    /// the column up/down movement tries to keep, reset by any other movement
+    pub desired_column: Option<u16>,
+}
+
+/// Byte offset of the start of every line
+fn line_starts(text: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+        .collect()
+}
+
+fn line_of(line_starts: &[usize], index: usize) -> usize {
+    line_starts.partition_point(|start| *start <= index) - 1
+}
+
+fn line_range(text: &str, line_starts: &[usize], line: usize) -> std::ops::Range<usize> {
+    let start = line_starts[line];
+    let end = line_starts
+        .get(line + 1)
+        .map(|next| next - 1)
+        .unwrap_or(text.len());
+    start..end
+}
+
+/// A multi line text editor, the app only owns the `String` while the caret,
+/// selection and scroll offsets are kept in memory
+pub struct TextArea<'a> {
+    text: &'a mut String,
+    id_source: Option<Id>,
+    desired_size: VecI2,
+    line_numbers: bool,
+    tab_inserts: bool,
+    tab_width: u16,
+    style: Option<Style>,
+}
+
+impl<'a> TextArea<'a> {
+    pub fn new(text: &'a mut String) -> Self {
+        Self {
+            text,
+            id_source: None,
+            desired_size: VecI2::new(40, 10),
+            line_numbers: false,
+            tab_inserts: true,
+            tab_width: 4,
+            style: None,
+        }
+    }
+
+    pub fn with_id(mut self, source: impl std::hash::Hash) -> Self {
+        self.id_source = Some(Id::new(source));
+        self
+    }
+
+    pub fn desired_size(mut self, size: VecI2) -> Self {
+        self.desired_size = size;
+        self
+    }
+
+    pub fn line_numbers(mut self, show: bool) -> Self {
+        self.line_numbers = show;
+        self
+    }
+
+    /// When set tab indents instead of moving focus, esc gives up focus so tab can be used again
+    pub fn tab_inserts(mut self, tab_inserts: bool) -> Self {
+        self.tab_inserts = tab_inserts;
+        self
+    }
+
+    /// How many spaces a tab inserts, tabs already in the text are shown this wide
+    pub fn tab_width(mut self, width: u16) -> Self {
+        self.tab_width = width;
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui) -> Response {
+        let id = match self.id_source {
+            Some(source) => ui.id().with(source),
+            None => Id::new(ui.next_id_source()),
+        };
+        let ctx = ui.ctx().clone();
+        let default_style = *ctx.style().borrow();
+
+        let area = ui.allocate_size(self.desired_size);
+        let mut response = ui.interact_scroll(id, area);
+
+        let mut state = ctx.get_memory_or(id, TextAreaState::default());
+        state.cursor.clamp(self.text);
+
+        let mut starts = line_starts(self.text);
+        let gutter = if self.line_numbers {
+            starts.len().to_string().len() as u16 + 1
+        } else {
+            0
+        };
+        let text_area = Rect::new_pos_size(
+            VecI2::new(area.x + gutter, area.y),
+            VecI2::new(area.width.saturating_sub(gutter), area.height),
+        );
+        let rows = text_area.height.max(1) as usize;
+        let tab_width = self.tab_width;
+
+        let index_at = |text: &str, starts: &[usize], pos: VecI2, state: &TextAreaState| {
+            let line =
+                (state.scroll_y + pos.y.saturating_sub(text_area.y) as usize).min(starts.len() - 1);
+            let range = line_range(text, starts, line);
+            let column = pos.x.saturating_sub(text_area.x) + state.scroll_x;
+            range.start + index_at_column(&text[range], column, None, tab_width)
+        };
+
+        let mut caret_moved = false;
+        let mouse = ctx.input().mouse;
+        let mut focused = response.focused;
+        match (mouse.buttons[0], mouse.position) {
+            (MouseButtonState::Down(pos), _) if area.contains(pos) && response.pressed() => {
+                response.request_focus(&ctx);
+                focused = true;
+                state.cursor = TextCursor::new(index_at(self.text, &starts, pos, &state));
+                state.desired_column = None;
+            }
+            (MouseButtonState::Down(_), _) if focused => {
+                response.surrender_focus(&ctx);
+                focused = false;
+            }
+            (MouseButtonState::Drag { .. }, Some(pos)) if focused && response.dragged() => {
+                let index = index_at(self.text, &starts, pos, &state);
+                state.cursor.move_to(index, true);
+                state.desired_column = None;
+                caret_moved = true;
+            }
+            _ => {}
+        }
+
+        if response.scrolled() {
+            let (dx, dy) = response.scroll_delta();
+            state.scroll_x = state.scroll_x.saturating_add_signed(dx * 3);
+            state.scroll_y = state.scroll_y.saturating_add_signed(-dy as isize * 3);
+        }
+
+        if focused {
+            ctx.lock_focus(FocusLock {
+                horizontal: true,
+                vertical: true,
+                tab: self.tab_inserts,
+            });
+
+            let tab = " ".repeat(self.tab_width as usize);
+            let keyboard = &ctx.input().keyboard;
+            let undoer = load_undoer(&ctx, id);
+            let mut undoer = undoer.borrow_mut();
+            for (index, key) in keyboard.events.iter().enumerate() {
+                if ctx.is_key_consumed(index) {
+                    continue;
+                }
+                if let Some(changed) =
+                    apply_undo_key(&ctx, index, key, &mut undoer, self.text, &mut state.cursor)
+                {
+                    response.changed |= changed;
+                    state.desired_column = None;
+                    caret_moved = true;
+                    starts = line_starts(self.text);
+                    continue;
+                }
+
+                let was_changed = std::mem::take(&mut response.changed);
+                let mut group = undo_group(key);
+                let before = (!undoer.merges(group)).then(|| (self.text.clone(), state.cursor));
+                let clipboard =
+                    apply_clipboard_key(&ctx, index, key, self.text, &mut state.cursor, true);
+                if clipboard.is_some() {
+                    starts = line_starts(self.text);
+                }
+                let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+                let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+                let line = line_of(&starts, state.cursor.cursor);
+                let range = line_range(self.text, &starts, line);
+                let column = line_width(
+                    &self.text[range.start..state.cursor.cursor],
+                    None,
+                    tab_width,
+                );
+
+                let vertical = |lines: isize, state: &mut TextAreaState| {
+                    let desired = *state.desired_column.get_or_insert(column);
+                    let target = line.saturating_add_signed(lines).min(starts.len() - 1);
+                    let range = line_range(self.text, &starts, target);
+                    let index =
+                        range.start + index_at_column(&self.text[range], desired, None, tab_width);
+                    state.cursor.move_to(index, shift);
+                };
+
+                match clipboard {
+                    Some(ClipboardEdit::Changed(changed)) => {
+                        group = None;
+                        response.changed = changed;
+                    }
+                    Some(ClipboardEdit::Paste(paste)) => {
+                        group = None;
+                        let paste = paste.replace("\r\n", "\n").replace('\t', &tab);
+                        response.changed = insert_text(self.text, &mut state.cursor, &paste, None);
+                    }
+                    None => match key.code {
+                        KeyCode::Up => vertical(-1, &mut state),
+                        KeyCode::Down => vertical(1, &mut state),
+                        KeyCode::PageUp => vertical(-(rows as isize), &mut state),
+                        KeyCode::PageDown => vertical(rows as isize, &mut state),
+                        KeyCode::Home if ctrl => state.cursor.move_to(0, shift),
+                        KeyCode::End if ctrl => state.cursor.move_to(self.text.len(), shift),
+                        KeyCode::Home => state.cursor.move_to(range.start, shift),
+                        KeyCode::End => state.cursor.move_to(range.end, shift),
+                        KeyCode::Esc => {
+                            response.surrender_focus(&ctx);
+                        }
+                        KeyCode::Enter => {
+                            response.changed |=
+                                insert_text(self.text, &mut state.cursor, "\n", None);
+                            // enter made a new line, it should not click the area as well
+                            response.buttons[0] = MouseButtonState::UnPressed;
+                        }
+                        KeyCode::Tab if self.tab_inserts => {
+                            response.changed |=
+                                insert_text(self.text, &mut state.cursor, &tab, None);
+                        }
+                        KeyCode::BackTab if self.tab_inserts => {
+                            // dedent the current line
+                            let indent = self.text[range.clone()]
+                                .chars()
+                                .take(self.tab_width as usize)
+                                .take_while(|c| *c == ' ')
+                                .count();
+                            if indent > 0 {
+                                self.text
+                                    .replace_range(range.start..range.start + indent, "");
+                                for index in [&mut state.cursor.cursor, &mut state.cursor.anchor] {
+                                    *index = if *index >= range.start + indent {
+                                        *index - indent
+                                    } else {
+                                        (*index).min(range.start)
+                                    };
+                                }
+                                response.changed = true;
+                            }
+                        }
+                        _ => {
+                            response.changed |=
+                                apply_key(self.text, &mut state.cursor, key, None).unwrap_or(false);
+                        }
+                    },
+                }
+
+                if !matches!(
+                    key.code,
+                    KeyCode::Up | KeyCode::Down | KeyCode::PageUp | KeyCode::PageDown
+                ) {
+                    state.desired_column = None;
+                }
+                if response.changed {
+                    undoer.record_snapshot(before, group);
+                } else {
+                    undoer.end_group();
+                }
+                response.changed |= was_changed;
+                caret_moved = true;
+                starts = line_starts(self.text);
+            }
+
+            if let Some(paste) = response.paste.take() {
+                let paste = paste.replace("\r\n", "\n").replace('\t', &tab);
+                let before = (self.text.clone(), state.cursor);
+                if insert_text(self.text, &mut state.cursor, &paste, None) {
+                    undoer.record(before, None);
+                    response.changed = true;
+                }
+                state.desired_column = None;
+                caret_moved = true;
+                starts = line_starts(self.text);
+            }
+        }
+
+        let caret_line = line_of(&starts, state.cursor.cursor);
+        let caret_range = line_range(self.text, &starts, caret_line);
+        let caret_col = line_width(
+            &self.text[caret_range.start..state.cursor.cursor],
+            None,
+            tab_width,
+        );
+
+        // keep the caret in view when it moved, otherwise let the wheel scroll freely
+        if caret_moved {
+            let width = text_area.width.max(1);
+            if caret_col < state.scroll_x {
+                state.scroll_x = caret_col;
+            } else if caret_col >= state.scroll_x + width {
+                state.scroll_x = caret_col + 1 - width;
+            }
+            if caret_line < state.scroll_y {
+                state.scroll_y = caret_line;
+            } else if caret_line >= state.scroll_y + rows {
+                state.scroll_y = caret_line + 1 - rows;
+            }
+        }
+        state.scroll_y = state.scroll_y.min(starts.len() - 1);
+
+        let style = self.style.unwrap_or(if focused {
+            default_style.text_edit_focused
+        } else {
+            default_style.text_edit
+        });
+        let selection = if focused {
+            state.cursor.selection()
+        } else {
+            0..0
+        };
+
+        for row in 0..text_area.height {
+            let line = state.scroll_y + row as usize;
+            let y = area.y + row;
+
+            if self.line_numbers {
+                let number = if line < starts.len() {
+                    format!("{:>1$} ", line + 1, gutter as usize - 1)
+                } else {
+                    " ".repeat(gutter as usize)
+                };
+                let gutter_rect = Rect::new_pos_size(VecI2::new(area.x, y), VecI2::new(gutter, 1));
+                let mut number = StyledText::styled(number, default_style.placeholder);
+                number.underline(false);
+                ui.draw(
+                    &number.text,
+                    number.style,
+                    gutter_rect.top_left(),
+                    gutter_rect,
+                );
+            }
+
+            let row_area =
+                Rect::new_pos_size(VecI2::new(text_area.x, y), VecI2::new(text_area.width, 1));
+            if line < starts.len() {
+                let range = line_range(self.text, &starts, line);
+                draw_line(
+                    ui,
+                    &self.text[range.clone()],
+                    range.start,
+                    row_area,
+                    state.scroll_x,
+                    selection.clone(),
+                    None,
+                    tab_width,
+                    style,
+                    default_style.text_selection,
+                );
+            } else {
+                draw_line(ui, "", 0, row_area, 0, 0..0, None, 0, style, style);
+            }
+        }
+
+        let visible_line = caret_line.checked_sub(state.scroll_y).filter(|l| *l < rows);
+        if let (true, Some(line), Some(col)) =
+            (focused, visible_line, caret_col.checked_sub(state.scroll_x))
+        {
+            if col < text_area.width {
+                ctx.set_cursor(Cursor {
+                    x: text_area.x + col,
+                    y: text_area.y + line as u16,
+                });
+            }
+        }
+
+        ctx.insert_into_memory(id, state);
+        response
+    }
+}
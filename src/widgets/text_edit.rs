@@ -0,0 +1,669 @@
+use std::{cell::RefCell, ops::Range, rc::Rc};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{
+    context::{Context, Cursor, FocusLock},
+    id::Id,
+    input::{mouse::MouseButtonState, shortcut::any_matches},
+    math_util::{Rect, VecI2},
+    response::Response,
+    style::Style,
+    ui::Ui,
+    undo::{UndoGroup, Undoer},
+};
+
+/// Caret and selection anchor of a text widget, both are byte offsets on char boundaries
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TextCursor {
+    pub cursor: usize,
+    pub anchor: usize,
+}
+
+impl TextCursor {
+    pub fn new(index: usize) -> Self {
+        Self {
+            cursor: index,
+            anchor: index,
+        }
+    }
+
+    pub fn has_selection(&self) -> bool {
+        self.cursor != self.anchor
+    }
+
+    pub fn selection(&self) -> Range<usize> {
+        self.cursor.min(self.anchor)..self.cursor.max(self.anchor)
+    }
+
+    pub fn move_to(&mut self, index: usize, select: bool) {
+        self.cursor = index;
+        if !select {
+            self.anchor = index;
+        }
+    }
+
+    /// Makes sure the cursor is still valid after the text was changed from outside
+    pub fn clamp(&mut self, text: &str) {
+        for index in [&mut self.cursor, &mut self.anchor] {
+            *index = (*index).min(text.len());
+            while !text.is_char_boundary(*index) {
+                *index -= 1;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TextEditState {
+    pub cursor: TextCursor,
+    /// how many cells of the text are scrolled out of view to the left
+    pub scroll: u16,
+}
+
+pub(crate) fn prev_char(text: &str, index: usize) -> usize {
+    text[..index]
+        .char_indices()
+        .next_back()
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+pub(crate) fn next_char(text: &str, index: usize) -> usize {
+    text[index..]
+        .chars()
+        .next()
+        .map(|c| index + c.len_utf8())
+        .unwrap_or(index)
+}
+
+fn char_class(c: char) -> u8 {
+    if c.is_whitespace() {
+        0
+    } else if c.is_alphanumeric() || c == '_' {
+        1
+    } else {
+        2
+    }
+}
+
+pub(crate) fn prev_word(text: &str, index: usize) -> usize {
+    let mut chars = text[..index].char_indices().rev().peekable();
+    while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    let Some(&(mut start, first)) = chars.peek() else {
+        return 0;
+    };
+    let class = char_class(first);
+    while let Some((i, _)) = chars.next_if(|(_, c)| char_class(*c) == class) {
+        start = i;
+    }
+    start
+}
+
+pub(crate) fn next_word(text: &str, index: usize) -> usize {
+    let mut chars = text[index..].char_indices().peekable();
+    while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    let Some(&(_, first)) = chars.peek() else {
+        return text.len();
+    };
+    let class = char_class(first);
+    while chars.next_if(|(_, c)| char_class(*c) == class).is_some() {}
+    chars.peek().map(|(i, _)| index + i).unwrap_or(text.len())
+}
+
+/// The cells `c` takes when it starts at `col`, a tab reaches up to the next
+/// multiple of `tab_width` and takes no space when `tab_width` is 0
+pub(crate) fn char_width(c: char, col: u16, mask: Option<&str>, tab_width: u16) -> u16 {
+    match mask {
+        Some(mask) => unicode_width::UnicodeWidthStr::width(mask) as u16,
+        None if c == '\t' && tab_width > 0 => tab_width - col % tab_width,
+        None => unicode_width::UnicodeWidthChar::width(c).unwrap_or(0) as u16,
+    }
+}
+
+pub(crate) fn str_width(text: &str, mask: Option<&str>) -> u16 {
+    line_width(text, mask, 0)
+}
+
+/// Width of `text` from the start of a line with tabs expanded
+pub(crate) fn line_width(text: &str, mask: Option<&str>, tab_width: u16) -> u16 {
+    text.chars()
+        .fold(0, |col, c| col + char_width(c, col, mask, tab_width))
+}
+
+/// The byte index of the char boundary closest to `column`
+pub(crate) fn index_at_column(
+    text: &str,
+    column: u16,
+    mask: Option<&str>,
+    tab_width: u16,
+) -> usize {
+    let mut col = 0;
+    for (i, c) in text.char_indices() {
+        let width = char_width(c, col, mask, tab_width);
+        if col + width.div_ceil(2) > column {
+            return i;
+        }
+        col += width;
+    }
+    text.len()
+}
+
+pub(crate) fn delete_selection(text: &mut String, cursor: &mut TextCursor) -> bool {
+    if !cursor.has_selection() {
+        return false;
+    }
+    let range = cursor.selection();
+    text.replace_range(range.clone(), "");
+    *cursor = TextCursor::new(range.start);
+    true
+}
+
+/// Replaces the selection with `insert`, keeping the text at most `max_len` chars long
+pub(crate) fn insert_text(
+    text: &mut String,
+    cursor: &mut TextCursor,
+    insert: &str,
+    max_len: Option<usize>,
+) -> bool {
+    let mut changed = delete_selection(text, cursor);
+    let insert = match max_len {
+        Some(max_len) => {
+            let available = max_len.saturating_sub(text.chars().count());
+            let end = insert
+                .char_indices()
+                .nth(available)
+                .map(|(i, _)| i)
+                .unwrap_or(insert.len());
+            &insert[..end]
+        }
+        None => insert,
+    };
+    if !insert.is_empty() {
+        text.insert_str(cursor.cursor, insert);
+        *cursor = TextCursor::new(cursor.cursor + insert.len());
+        changed = true;
+    }
+    changed
+}
+
+/// Undo history of a text widget, kept in memory under the widgets id
+pub type TextUndoer = Undoer<(String, TextCursor)>;
+
+/// The undo history of the text widget with `id`, shared so it is not copied every frame
+pub(crate) fn load_undoer(ctx: &Context, id: Id) -> Rc<RefCell<TextUndoer>> {
+    ctx.get_memory_or_create(id, || Rc::new(RefCell::new(TextUndoer::default())))
+}
+
+/// Which undo step an edit made by `key` gets merged into, shortcuts get their own step
+pub(crate) fn undo_group(key: &KeyEvent) -> Option<UndoGroup> {
+    let shortcut = key
+        .modifiers
+        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+    match key.code {
+        KeyCode::Char(_) if shortcut => None,
+        KeyCode::Char(_) | KeyCode::Enter | KeyCode::Tab => Some(UndoGroup::Typing),
+        KeyCode::Backspace | KeyCode::Delete => Some(UndoGroup::Deleting),
+        _ => None,
+    }
+}
+
+/// Handles the undo/redo shortcuts, returns `Some(changed)` when `key` was one of them
+pub(crate) fn apply_undo_key(
+    ctx: &Context,
+    index: usize,
+    key: &KeyEvent,
+    undoer: &mut TextUndoer,
+    text: &mut String,
+    cursor: &mut TextCursor,
+) -> Option<bool> {
+    let shortcuts = ctx.shortcuts().borrow();
+    let current = (text.clone(), *cursor);
+    let restored = if any_matches(&shortcuts.undo, key) {
+        undoer.undo(&current)
+    } else if any_matches(&shortcuts.redo, key) {
+        undoer.redo(&current)
+    } else {
+        return None;
+    };
+    ctx.consume_key(index);
+    Some(match restored {
+        Some((restored, restored_cursor)) => {
+            *text = restored;
+            *cursor = restored_cursor;
+            true
+        }
+        None => false,
+    })
+}
+
+/// A copy/cut/paste shortcut handled by [`apply_clipboard_key`]
+pub(crate) enum ClipboardEdit {
+    Changed(bool),
+    /// the widget still has to insert the clipboard contents
+    Paste(String),
+}
+
+/// Handles the copy/cut/paste shortcuts, `can_copy` is false for masked text
+pub(crate) fn apply_clipboard_key(
+    ctx: &Context,
+    index: usize,
+    key: &KeyEvent,
+    text: &mut String,
+    cursor: &mut TextCursor,
+    can_copy: bool,
+) -> Option<ClipboardEdit> {
+    let shortcuts = ctx.shortcuts().borrow();
+    let edit = if any_matches(&shortcuts.paste, key) {
+        ClipboardEdit::Paste(ctx.clipboard().borrow().get().to_owned())
+    } else if any_matches(&shortcuts.copy, key) {
+        if can_copy && cursor.has_selection() {
+            ctx.copy_text(&text[cursor.selection()]);
+        }
+        ClipboardEdit::Changed(false)
+    } else if any_matches(&shortcuts.cut, key) {
+        if can_copy && cursor.has_selection() {
+            ctx.copy_text(&text[cursor.selection()]);
+        }
+        ClipboardEdit::Changed(can_copy && delete_selection(text, cursor))
+    } else {
+        return None;
+    };
+    ctx.consume_key(index);
+    Some(edit)
+}
+
+/// Applies the edits shared by every text widget, returns `None` if the key isn't one of them
+/// and otherwise whether the text changed
+pub(crate) fn apply_key(
+    text: &mut String,
+    cursor: &mut TextCursor,
+    key: &KeyEvent,
+    max_len: Option<usize>,
+) -> Option<bool> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+    let changed = match key.code {
+        KeyCode::Char('a') if ctrl => {
+            cursor.anchor = 0;
+            cursor.cursor = text.len();
+            false
+        }
+        KeyCode::Char(c)
+            if !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+        {
+            insert_text(text, cursor, c.encode_utf8(&mut [0; 4]), max_len)
+        }
+        KeyCode::Backspace => {
+            if !delete_selection(text, cursor) {
+                let start = if ctrl {
+                    prev_word(text, cursor.cursor)
+                } else {
+                    prev_char(text, cursor.cursor)
+                };
+                cursor.anchor = start;
+                delete_selection(text, cursor)
+            } else {
+                true
+            }
+        }
+        KeyCode::Delete => {
+            if !delete_selection(text, cursor) {
+                let end = if ctrl {
+                    next_word(text, cursor.cursor)
+                } else {
+                    next_char(text, cursor.cursor)
+                };
+                cursor.anchor = end;
+                delete_selection(text, cursor)
+            } else {
+                true
+            }
+        }
+        KeyCode::Left => {
+            let index = if cursor.has_selection() && !shift && !ctrl {
+                cursor.selection().start
+            } else if ctrl {
+                prev_word(text, cursor.cursor)
+            } else {
+                prev_char(text, cursor.cursor)
+            };
+            cursor.move_to(index, shift);
+            false
+        }
+        KeyCode::Right => {
+            let index = if cursor.has_selection() && !shift && !ctrl {
+                cursor.selection().end
+            } else if ctrl {
+                next_word(text, cursor.cursor)
+            } else {
+                next_char(text, cursor.cursor)
+            };
+            cursor.move_to(index, shift);
+            false
+        }
+        _ => return None,
+    };
+    Some(changed)
+}
+
+/// Draws one line of text starting `scroll` cells in, highlighting `selection`
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn draw_line(
+    ui: &mut Ui,
+    line: &str,
+    line_start: usize,
+    area: Rect,
+    scroll: u16,
+    selection: Range<usize>,
+    mask: Option<&str>,
+    tab_width: u16,
+    style: Style,
+    selection_style: Style,
+) {
+    let mut segments: Vec<(String, Style)> = Vec::new();
+    let mut push = |text: &str, style: Style| match segments.last_mut() {
+        Some((last, last_style)) if *last_style == style => last.push_str(text),
+        _ => segments.push((text.to_owned(), style)),
+    };
+
+    let mut col = 0;
+    let mut drawn = 0;
+    for (i, c) in line.char_indices() {
+        let width = char_width(c, col, mask, tab_width);
+        let style = if selection.contains(&(line_start + i)) {
+            selection_style
+        } else {
+            style
+        };
+        if col < scroll {
+            // a wide char cut in half by the scroll
+            for _ in scroll..(col + width).min(scroll + area.width) {
+                push(" ", style);
+                drawn += 1;
+            }
+        } else if col + width > scroll + area.width {
+            break;
+        } else if c == '\t' && mask.is_none() {
+            push(&" ".repeat(width as usize), style);
+            drawn += width;
+        } else {
+            push(mask.unwrap_or(c.encode_utf8(&mut [0; 4])), style);
+            drawn += width;
+        }
+        col += width;
+    }
+    for _ in drawn..area.width {
+        push(" ", style);
+    }
+
+    let mut x = area.x;
+    for (text, style) in segments {
+        let width = str_width(&text, None);
+        ui.draw(&text, style, VecI2::new(x, area.y), area);
+        x += width;
+    }
+}
+
+/// A single line text input, its caret and selection are kept in memory
+pub struct TextEdit<'a> {
+    text: &'a mut String,
+    id_source: Option<Id>,
+    placeholder: Option<&'a str>,
+    password: bool,
+    max_len: Option<usize>,
+    desired_width: u16,
+    style: Option<Style>,
+}
+
+impl<'a> TextEdit<'a> {
+    pub fn new(text: &'a mut String) -> Self {
+        Self {
+            text,
+            id_source: None,
+            placeholder: None,
+            password: false,
+            max_len: None,
+            desired_width: 20,
+            style: None,
+        }
+    }
+
+    pub fn with_id(mut self, source: impl std::hash::Hash) -> Self {
+        self.id_source = Some(Id::new(source));
+        self
+    }
+
+    pub fn placeholder(mut self, placeholder: &'a str) -> Self {
+        self.placeholder = Some(placeholder);
+        self
+    }
+
+    pub fn password(mut self, password: bool) -> Self {
+        self.password = password;
+        self
+    }
+
+    /// Maximum length in chars
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    pub fn desired_width(mut self, width: u16) -> Self {
+        self.desired_width = width;
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui) -> Response {
+        let id = match self.id_source {
+            Some(source) => ui.id().with(source),
+            None => Id::new(ui.next_id_source()),
+        };
+        let ctx = ui.ctx().clone();
+        let default_style = *ctx.style().borrow();
+        let mask = self.password.then_some(default_style.password);
+
+        let area = ui.allocate_size(VecI2::new(self.desired_width, 1));
+        let mut response = ui.interact(id, area);
+
+        let mut state = ctx.get_memory_or(id, TextEditState::default());
+        state.cursor.clamp(self.text);
+
+        let mouse = ctx.input().mouse;
+        let column_at = |pos: VecI2, scroll: u16| pos.x.saturating_sub(area.x) + scroll;
+        let mut focused = response.focused;
+        match (mouse.buttons[0], mouse.position) {
+            (MouseButtonState::Down(pos), _) if area.contains(pos) && response.pressed() => {
+                response.request_focus(&ctx);
+                focused = true;
+                let index = index_at_column(self.text, column_at(pos, state.scroll), mask, 0);
+                state.cursor = TextCursor::new(index);
+            }
+            (MouseButtonState::Down(_), _) if focused => {
+                response.surrender_focus(&ctx);
+                focused = false;
+            }
+            (MouseButtonState::Drag { .. }, Some(pos)) if focused && response.dragged() => {
+                // dragging past the edges scrolls the text
+                if pos.x < area.x {
+                    state.scroll = state.scroll.saturating_sub(1);
+                } else if pos.x >= area.x + area.width {
+                    state.scroll = state.scroll.saturating_add(1);
+                }
+                let index = index_at_column(self.text, column_at(pos, state.scroll), mask, 0);
+                state.cursor.move_to(index, true);
+            }
+            _ => {}
+        }
+
+        if focused {
+            ctx.lock_focus(FocusLock {
+                horizontal: true,
+                ..Default::default()
+            });
+
+            let keyboard = &ctx.input().keyboard;
+            if !keyboard.events.is_empty() || response.paste.is_some() {
+                let undoer = load_undoer(&ctx, id);
+                let mut undoer = undoer.borrow_mut();
+
+                for (index, key) in keyboard.events.iter().enumerate() {
+                    if ctx.is_key_consumed(index) {
+                        continue;
+                    }
+                    if let Some(changed) =
+                        apply_undo_key(&ctx, index, key, &mut undoer, self.text, &mut state.cursor)
+                    {
+                        response.changed |= changed;
+                        continue;
+                    }
+
+                    let mut group = undo_group(key);
+                    let before = (!undoer.merges(group)).then(|| (self.text.clone(), state.cursor));
+                    let clipboard = apply_clipboard_key(
+                        &ctx,
+                        index,
+                        key,
+                        self.text,
+                        &mut state.cursor,
+                        mask.is_none(),
+                    );
+                    let changed = match clipboard {
+                        Some(ClipboardEdit::Changed(changed)) => {
+                            group = None;
+                            changed
+                        }
+                        Some(ClipboardEdit::Paste(paste)) => {
+                            group = None;
+                            let paste = paste.replace(['\r', '\n'], " ");
+                            insert_text(self.text, &mut state.cursor, &paste, self.max_len)
+                        }
+                        None => match key.code {
+                            KeyCode::Home => {
+                                let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+                                state.cursor.move_to(0, shift);
+                                false
+                            }
+                            KeyCode::End => {
+                                let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+                                state.cursor.move_to(self.text.len(), shift);
+                                false
+                            }
+                            KeyCode::Esc | KeyCode::Enter => {
+                                response.surrender_focus(&ctx);
+                                // enter gave up focus, it should not click the edit as well
+                                response.buttons[0] = MouseButtonState::UnPressed;
+                                false
+                            }
+                            _ => apply_key(self.text, &mut state.cursor, key, self.max_len)
+                                .unwrap_or(false),
+                        },
+                    };
+                    if changed {
+                        undoer.record_snapshot(before, group);
+                        response.changed = true;
+                    } else {
+                        undoer.end_group();
+                    }
+                }
+
+                if let Some(paste) = response.paste.take() {
+                    // no newlines in a single line
+                    let paste = paste.replace(['\r', '\n'], " ");
+                    let before = (self.text.clone(), state.cursor);
+                    if insert_text(self.text, &mut state.cursor, &paste, self.max_len) {
+                        undoer.record(before, None);
+                        response.changed = true;
+                    }
+                }
+            }
+        }
+
+        // keep the caret in view
+        let caret_col = str_width(&self.text[..state.cursor.cursor], mask);
+        let total_width = str_width(self.text, mask);
+        let width = area.width.max(1);
+        if caret_col < state.scroll {
+            state.scroll = caret_col;
+        } else if caret_col >= state.scroll + width {
+            state.scroll = caret_col + 1 - width;
+        }
+        state.scroll = state.scroll.min((total_width + 1).saturating_sub(width));
+
+        let style = self.style.unwrap_or(if focused {
+            default_style.text_edit_focused
+        } else {
+            default_style.text_edit
+        });
+
+        match self.placeholder {
+            Some(placeholder) if self.text.is_empty() => draw_line(
+                ui,
+                placeholder,
+                0,
+                area,
+                0,
+                0..0,
+                None,
+                0,
+                default_style.placeholder,
+                default_style.placeholder,
+            ),
+            _ => draw_line(
+                ui,
+                self.text,
+                0,
+                area,
+                state.scroll,
+                if focused {
+                    state.cursor.selection()
+                } else {
+                    0..0
+                },
+                mask,
+                0,
+                style,
+                default_style.text_selection,
+            ),
+        }
+
+        if focused && area.width > 0 {
+            ctx.set_cursor(Cursor {
+                x: area.x + caret_col - state.scroll,
+                y: area.y,
+            });
+        }
+
+        ctx.insert_into_memory(id, state);
+        response
+    }
+}
+
+#[test]
+pub fn test_word_movement() {
+    let text = "héllo, 世界  wörld";
+    assert_eq!(next_word(text, 0), "héllo".len());
+    assert_eq!(next_word(text, "héllo".len()), "héllo,".len());
+    assert_eq!(prev_word(text, text.len()), "héllo, 世界  ".len());
+    assert_eq!(prev_word(text, "héllo, 世".len()), "héllo, ".len());
+    assert_eq!(index_at_column(text, 8, None, 0), "héllo, 世".len());
+    assert_eq!(line_width("a\tb\t", None, 4), 8);
+    assert_eq!(index_at_column("a\tb", 3, None, 4), "a\t".len());
+
+    let mut text = String::from("abc");
+    let mut cursor = TextCursor {
+        cursor: 1,
+        anchor: 2,
+    };
+    assert!(insert_text(&mut text, &mut cursor, "xyz", Some(4)));
+    assert_eq!(text, "axyc");
+    assert_eq!(cursor, TextCursor::new(3));
+}
@@ -0,0 +1,36 @@
+use crate::{response::Response, style::StyledText, ui::Ui};
+
+use super::checkbox::show_glyph_label;
+
+/// An on/off switch bound to a `bool`
+pub struct Toggle<'a, 'b> {
+    on: &'a mut bool,
+    text: StyledText<'b>,
+}
+
+impl<'a, 'b> Toggle<'a, 'b> {
+    pub fn new(on: &'a mut bool) -> Self {
+        Self {
+            on,
+            text: StyledText::default(),
+        }
+    }
+
+    /// Label drawn after the switch
+    pub fn text(mut self, text: impl Into<StyledText<'b>>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui) -> Response {
+        let toggles = ui.ctx().style().borrow().toggles;
+        let glyph = if *self.on { toggles.on } else { toggles.off };
+
+        let mut response = show_glyph_label(ui, glyph, *self.on, &self.text);
+        if response.clicked() {
+            *self.on ^= true;
+            response.mark_changed();
+        }
+        response
+    }
+}
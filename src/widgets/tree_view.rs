@@ -0,0 +1,443 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    rc::Rc,
+};
+
+use crossterm::event::KeyCode;
+
+use crate::{
+    containers::list_view::{ListView, ListViewState, SelectionMode},
+    context::FocusLock,
+    id::Id,
+    input::mouse::MouseButtonState,
+    math_util::VecI2,
+    response::Response,
+    ui::Ui,
+};
+
+/// A node as it is shown, in the order of the tree with collapsed nodes left out
+#[derive(Debug, Clone)]
+struct FlatNode<N> {
+    node: N,
+    depth: u16,
+    /// last of its siblings
+    last: bool,
+    /// position of the parent row
+    parent: Option<usize>,
+    has_children: bool,
+    /// for every ancestor below the roots, if a guide line passes through
+    guides: Vec<bool>,
+}
+
+/// Expanded nodes and loaded children of a [`TreeView`], shared with the
+/// memory so changes show on the next frame
+pub struct TreeViewState<N> {
+    expanded: HashSet<N>,
+    children: HashMap<N, Vec<N>>,
+    rows: Rc<[FlatNode<N>]>,
+    roots: Vec<N>,
+    dirty: bool,
+}
+
+impl<N> Default for TreeViewState<N> {
+    fn default() -> Self {
+        Self {
+            expanded: HashSet::new(),
+            children: HashMap::new(),
+            rows: Rc::from(Vec::new()),
+            roots: Vec::new(),
+            dirty: true,
+        }
+    }
+}
+
+impl<N: Clone + Hash + Eq + 'static> TreeViewState<N> {
+    /// The state of the tree view made with `id_source` in `ui`
+    pub fn load(ui: &Ui, id_source: impl Hash) -> Rc<RefCell<Self>> {
+        ui.ctx()
+            .get_memory_or_create(ui.id().with(Id::new(id_source)), || {
+                Rc::new(RefCell::new(Self::default()))
+            })
+    }
+
+    pub fn is_expanded(&self, node: &N) -> bool {
+        self.expanded.contains(node)
+    }
+
+    pub fn expand(&mut self, node: N) {
+        self.dirty |= self.expanded.insert(node);
+    }
+
+    pub fn collapse(&mut self, node: &N) {
+        self.dirty |= self.expanded.remove(node);
+    }
+
+    pub fn toggle(&mut self, node: &N) {
+        if self.is_expanded(node) {
+            self.collapse(node);
+        } else {
+            self.expand(node.clone());
+        }
+    }
+
+    /// Forgets the children of `node`, they are loaded again the next time they are shown
+    pub fn reload(&mut self, node: &N) {
+        self.dirty |= self.children.remove(node).is_some();
+    }
+
+    pub fn reload_all(&mut self) {
+        self.children.clear();
+        self.dirty = true;
+    }
+
+    fn flatten(
+        &mut self,
+        loader: &mut Loader<N>,
+        nodes: &[N],
+        depth: u16,
+        parent: Option<usize>,
+        guides: &mut Vec<bool>,
+        rows: &mut Vec<FlatNode<N>>,
+    ) {
+        for (i, node) in nodes.iter().enumerate() {
+            let last = i + 1 == nodes.len();
+            let expanded = self.expanded.contains(node);
+            // children are only loaded once a node is expanded
+            if expanded && !self.children.contains_key(node) {
+                let children = (loader.children)(node);
+                self.children.insert(node.clone(), children);
+            }
+            let has_children = match self.children.get(node) {
+                Some(children) => !children.is_empty(),
+                None => loader.has_children.as_ref().is_none_or(|has| has(node)),
+            };
+            rows.push(FlatNode {
+                node: node.clone(),
+                depth,
+                last,
+                parent,
+                has_children,
+                guides: guides.clone(),
+            });
+            if expanded && has_children {
+                let children = self.children[node].clone();
+                if depth > 0 {
+                    guides.push(!last);
+                }
+                self.flatten(
+                    loader,
+                    &children,
+                    depth + 1,
+                    Some(rows.len() - 1),
+                    guides,
+                    rows,
+                );
+                if depth > 0 {
+                    guides.pop();
+                }
+            }
+        }
+    }
+
+    /// Lays the tree out again, keeping the cursor and selection on the same nodes
+    fn rebuild(&mut self, loader: &mut Loader<N>, list: &mut ListViewState) {
+        let old = self.rows.clone();
+        let mut rows = Vec::new();
+        let roots = self.roots.clone();
+        self.flatten(loader, &roots, 0, None, &mut Vec::new(), &mut rows);
+        self.dirty = false;
+
+        let positions: HashMap<&N, usize> = rows
+            .iter()
+            .enumerate()
+            .map(|(position, row)| (&row.node, position))
+            .collect();
+        // a hidden node moves to its closest shown ancestor
+        let moved = |mut position: usize| loop {
+            let row = old.get(position)?;
+            if let Some(new) = positions.get(&row.node) {
+                return Some(*new);
+            }
+            position = row.parent?;
+        };
+        let selected: Vec<usize> = list
+            .selection
+            .iter()
+            .filter_map(|position| positions.get(&old.get(position)?.node).copied())
+            .collect();
+        list.selection.clear();
+        for position in selected {
+            list.selection.select(position..position + 1);
+        }
+        list.cursor = moved(list.cursor).unwrap_or(list.cursor);
+        list.anchor = moved(list.anchor).unwrap_or(list.anchor);
+        self.rows = Rc::from(rows);
+    }
+}
+
+type LoadChildren<'a, N> = Box<dyn FnMut(&N) -> Vec<N> + 'a>;
+type HasChildren<'a, N> = Box<dyn Fn(&N) -> bool + 'a>;
+
+struct Loader<'a, N> {
+    children: LoadChildren<'a, N>,
+    has_children: Option<HasChildren<'a, N>>,
+}
+
+/// What a node is shown as
+#[derive(Debug, Clone, Copy)]
+pub struct TreeRow<'n, N> {
+    pub node: &'n N,
+    pub depth: u16,
+    pub expanded: bool,
+    pub has_children: bool,
+    pub selected: bool,
+    /// the keyboard cursor is on this node
+    pub cursor: bool,
+}
+
+pub struct TreeViewResponse<N> {
+    pub response: Response,
+    pub selected: Vec<N>,
+    /// the node the keyboard cursor is on
+    pub cursor: Option<N>,
+    /// node enter was pressed on or that was double clicked
+    pub activated: Option<N>,
+}
+
+/// Nested nodes with guide lines, only the nodes in view are drawn so large trees
+/// stay fast. Children are asked for the first time their parent is expanded.
+/// Right expands or moves to the first child, left collapses or moves to the parent,
+/// the rest of the keys and the selection work like in a [`ListView`]
+pub struct TreeView<'a, N> {
+    id_source: Id,
+    roots: Vec<N>,
+    loader: Loader<'a, N>,
+    mode: SelectionMode,
+    size: Option<VecI2>,
+}
+
+impl<'a, N: Clone + Hash + Eq + 'static> TreeView<'a, N> {
+    pub fn new(
+        id_source: impl Hash,
+        roots: impl IntoIterator<Item = N>,
+        children: impl FnMut(&N) -> Vec<N> + 'a,
+    ) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            roots: roots.into_iter().collect(),
+            loader: Loader {
+                children: Box::new(children),
+                has_children: None,
+            },
+            mode: SelectionMode::default(),
+            size: None,
+        }
+    }
+
+    /// Tells if a node can be expanded without loading its children, nodes are
+    /// expandable until they are loaded by default
+    pub fn has_children(mut self, has_children: impl Fn(&N) -> bool + 'a) -> Self {
+        self.loader.has_children = Some(Box::new(has_children));
+        self
+    }
+
+    pub fn selection_mode(mut self, mode: SelectionMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Defaults to all the space available
+    pub fn desired_size(mut self, size: VecI2) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn show(
+        mut self,
+        ui: &mut Ui,
+        mut show_node: impl FnMut(&mut Ui, TreeRow<N>),
+    ) -> TreeViewResponse<N> {
+        let ctx = ui.ctx().clone();
+        let id = ui.id().with(self.id_source);
+        let default_style = *ctx.style().borrow();
+        let lines = default_style.lines;
+        let rows_source = id.with("rows");
+        let list_id = ListView::id(ui, rows_source);
+        let size = self.size.unwrap_or_else(|| ui.get_max().size());
+
+        let shared =
+            ctx.get_memory_or_create(id, || Rc::new(RefCell::new(TreeViewState::<N>::default())));
+        let mut state = shared.borrow_mut();
+        let mut list: ListViewState = ctx.get_memory_or(list_id, ListViewState::default());
+        if state.roots != self.roots {
+            state.roots = self.roots.clone();
+            state.dirty = true;
+        }
+        if state.dirty {
+            state.rebuild(&mut self.loader, &mut list);
+        }
+
+        if ctx.is_focused(list_id) && !state.rows.is_empty() {
+            ctx.lock_focus(FocusLock {
+                horizontal: true,
+                ..Default::default()
+            });
+            for key in &ctx.input().keyboard.events {
+                let cursor = list.cursor.min(state.rows.len() - 1);
+                let row = state.rows[cursor].clone();
+                let expanded = state.is_expanded(&row.node);
+                let moved = match key.code {
+                    KeyCode::Right if row.has_children && !expanded => {
+                        state.expand(row.node);
+                        None
+                    }
+                    KeyCode::Right => state
+                        .rows
+                        .get(cursor + 1)
+                        .filter(|next| next.parent == Some(cursor))
+                        .map(|_| cursor + 1),
+                    KeyCode::Left if expanded => {
+                        state.collapse(&row.node);
+                        None
+                    }
+                    KeyCode::Left => row.parent,
+                    _ => None,
+                };
+                if let Some(position) = moved {
+                    list.move_cursor(self.mode, position, false, false);
+                    // keep the cursor in view
+                    list.scroll = list
+                        .scroll
+                        .min(position)
+                        .max((position + 1).saturating_sub(size.y as usize));
+                }
+                if state.dirty {
+                    state.rebuild(&mut self.loader, &mut list);
+                }
+            }
+        }
+        ctx.insert_into_memory(list_id, list);
+
+        let rows = state.rows.clone();
+        let expanded = state.expanded.clone();
+        // not borrowed while the nodes are shown so `show_node` can load the state
+        drop(state);
+        let list = ListView::new(rows_source, rows.len())
+            .selection_mode(self.mode)
+            .desired_size(size)
+            .show(ui, |ui, row| {
+                let flat = &rows[row.index];
+                let is_expanded = expanded.contains(&flat.node);
+                let mut prefix = String::new();
+                for guide in &flat.guides {
+                    prefix.push_str(if *guide { lines.vertical } else { " " });
+                    prefix.push(' ');
+                }
+                if flat.depth > 0 {
+                    prefix.push_str(if flat.last {
+                        lines.bottom_left
+                    } else {
+                        lines.vertical_right
+                    });
+                    prefix.push_str(lines.horizontal);
+                }
+                prefix.push_str(match (flat.has_children, is_expanded) {
+                    (true, true) => default_style.pointers.down,
+                    (true, false) => default_style.pointers.right,
+                    (false, _) if flat.depth > 0 => lines.horizontal,
+                    (false, _) => " ",
+                });
+                prefix.push(' ');
+
+                let style = if row.selected {
+                    default_style.list_highlight
+                } else {
+                    default_style.button
+                };
+                let rect = ui.get_max();
+                ui.draw(&prefix, style, rect.top_left(), rect);
+                ui.add_space_primary_direction(flat.depth * 2 + 2);
+                show_node(
+                    ui,
+                    TreeRow {
+                        node: &flat.node,
+                        depth: flat.depth,
+                        expanded: is_expanded,
+                        has_children: flat.has_children,
+                        selected: row.selected,
+                        cursor: row.cursor,
+                    },
+                );
+            });
+
+        // clicking the arrow in front of a node expands or collapses it
+        if let MouseButtonState::Down(pos) = ctx.input().mouse.buttons[0] {
+            let row = rows.get(list.cursor).filter(|row| row.has_children);
+            if let Some(row) = row.filter(|_| list.response.pressed()) {
+                if pos.x == list.response.rect.x + row.depth * 2 {
+                    shared.borrow_mut().toggle(&row.node);
+                    ctx.request_redraw();
+                }
+            }
+        }
+
+        TreeViewResponse {
+            response: list.response,
+            selected: list
+                .selection
+                .iter()
+                .filter_map(|position| rows.get(position))
+                .map(|row| row.node.clone())
+                .collect(),
+            cursor: rows.get(list.cursor).map(|row| row.node.clone()),
+            activated: list
+                .activated
+                .and_then(|position| rows.get(position))
+                .map(|row| row.node.clone()),
+        }
+    }
+}
+
+#[test]
+pub fn test_flatten_tree() {
+    let mut loaded = Vec::new();
+    let mut loader = Loader {
+        children: Box::new(|node: &u32| {
+            loaded.push(*node);
+            if *node < 10 {
+                (node * 10 + 10..node * 10 + 13).collect()
+            } else {
+                Vec::new()
+            }
+        }),
+        has_children: Some(Box::new(|node: &u32| *node < 10)),
+    };
+    let mut state = TreeViewState::default();
+    let mut list = ListViewState::default();
+    state.roots = vec![0, 1];
+    state.expand(0);
+    state.expand(11);
+    state.rebuild(&mut loader, &mut list);
+
+    let nodes: Vec<u32> = state.rows.iter().map(|row| row.node).collect();
+    assert_eq!(nodes, vec![0, 10, 11, 12, 1]);
+    // 11 was expanded but has no children
+    assert!(!state.rows[2].has_children && state.rows[4].has_children);
+    assert_eq!(state.rows[3].parent, Some(0));
+    assert!(state.rows[3].last);
+
+    // the selection and cursor stay on their nodes, hidden ones move to the parent
+    list.selection.select(4..5);
+    list.cursor = 2;
+    state.collapse(&0);
+    state.rebuild(&mut loader, &mut list);
+    assert_eq!(state.rows.len(), 2);
+    assert!(list.selection.contains(1) && list.selection.len() == 1);
+    assert_eq!(list.cursor, 0);
+
+    drop(loader);
+    // children are loaded once, when first shown
+    assert_eq!(loaded, vec![0, 11]);
+}